@@ -0,0 +1,99 @@
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Expr, Field, LitInt, Result};
+
+/// Parsed `#[jce(...)]` of a field
+pub struct FieldAttr {
+    pub tag: Option<u8>,
+    pub required: bool,
+    pub default: Option<Expr>,
+    pub skip: bool,
+}
+
+impl FieldAttr {
+    pub fn from_field(field: &Field) -> Result<Self> {
+        let mut attr = Self {
+            tag: None,
+            required: false,
+            default: None,
+            skip: false,
+        };
+        for jce in field.attrs.iter().filter(|a| a.path().is_ident("jce")) {
+            jce.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    match lit.base10_parse::<u8>() {
+                        Ok(tag) => attr.tag = Some(tag),
+                        Err(_) => {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "jce tag must be an integer in 0..=255",
+                            ))
+                        }
+                    }
+                } else if meta.path.is_ident("required") {
+                    attr.required = true;
+                } else if meta.path.is_ident("default") {
+                    attr.default = Some(if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse()?
+                    } else {
+                        syn::parse_quote!(::core::default::Default::default())
+                    });
+                } else if meta.path.is_ident("skip") {
+                    attr.skip = true;
+                } else {
+                    return Err(meta.error("unknown jce attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if attr.required && attr.default.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "a required field can't have a default value",
+            ));
+        }
+        if attr.skip && attr.tag.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "a skipped field can't have a tag",
+            ));
+        }
+        if !attr.skip && attr.tag.is_none() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "missing `#[jce(tag = N)]`, or `#[jce(skip)]` to skip this field",
+            ));
+        }
+        Ok(attr)
+    }
+}
+
+/// `#[serde(...)]` attributes, copied as is
+pub fn serde_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|a| a.path().is_ident("serde"))
+}
+
+/// Keys of `#[serde(...)]` attributes, e.g. `rename` & `with`
+pub fn serde_keys(attrs: &[Attribute]) -> Result<Vec<String>> {
+    let mut keys = Vec::new();
+    for serde in serde_attrs(attrs) {
+        serde.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                keys.push(ident.to_string());
+            }
+            skip_value(&meta)
+        })?;
+    }
+    Ok(keys)
+}
+
+/// Skip `= value` or `(...)` of a nested meta
+fn skip_value(meta: &ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|meta| skip_value(&meta))?;
+    }
+    Ok(())
+}
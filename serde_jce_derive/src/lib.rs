@@ -0,0 +1,402 @@
+//! Derive macro of Jce format for `serde_jce`
+//!
+//! `#[derive(Jce)]` implements `serde::Serialize` & `serde::Deserialize` for a struct with named fields,
+//! each field is tagged by `#[jce(tag = N)]` instead of `#[serde(rename = "N")]`.
+//! Tags are checked at compile time.
+//!
+//! For a fieldless enum, it encodes the discriminant as `i32` like Jce enums,
+//! rather than the variant index used by `serde_derive`.
+//!
+//! Use it through the `derive` feature of `serde_jce`.
+//!
+//! # Field attribute
+//!
+//! * `#[jce(tag = N)]` - The tag of field, `N` must be in `0..=255` & unique in the struct
+//! * `#[jce(required)]` - Deserializing fails with `ErrorKind::MissingField` if the field is missing, even for `Option<T>`
+//! * `#[jce(default = EXPR)]` - The value of field if it is missing, a string literal is converted by `Into`
+//! * `#[jce(default)]` - Same as `#[jce(default = Default::default())]`
+//! * `#[jce(skip)]` - Neither serialize nor deserialize the field
+//!
+//! Field that is neither `required` nor has a `default` is optional,
+//! it is `Default::default()` if missing, just like `optional` fields in the IDL.
+//!
+//! Other `#[serde(...)]` attributes are kept, e.g. `#[serde(with = "serde_bytes")]`.
+//!
+//! # Container attribute
+//!
+//! * `#[jce(bare)]` - Bare struct, same as `#[serde(rename = "$serde_jce::bare")]`
+//!
+//! # Example
+//!
+//! ```
+//! use serde_jce_derive::Jce;
+//!
+//! #[derive(PartialEq, Debug, Jce)]
+//! struct Struct {
+//!     #[jce(tag = 0)]
+//!     v0: i8,
+//!     #[jce(tag = 1, default = "jce")]
+//!     v1: String,
+//!     #[jce(tag = 2, required)]
+//!     v2: i16,
+//! }
+//!
+//! let bytes = [0x0a, 0x21, 0x34, 0x56, 0x0b];
+//! let val = Struct {
+//!     v0: 0,
+//!     v1: "jce".to_owned(),
+//!     v2: 0x3456,
+//! };
+//! assert_eq!(serde_jce::from_bytes(&bytes), Ok(val));
+//! ```
+//!
+//! Duplicate tags are rejected at compile time
+//!
+//! ```compile_fail
+//! use serde_jce_derive::Jce;
+//!
+//! #[derive(Jce)]
+//! struct Struct {
+//!     #[jce(tag = 1)]
+//!     v0: i8,
+//!     #[jce(tag = 1)]
+//!     v1: i8,
+//! }
+//! ```
+//!
+//! And so are tags greater than 255
+//!
+//! ```compile_fail
+//! use serde_jce_derive::Jce;
+//!
+//! #[derive(Jce)]
+//! struct Struct {
+//!     #[jce(tag = 256)]
+//!     v0: i8,
+//! }
+//! ```
+//!
+//! And a required field with a default value
+//!
+//! ```compile_fail
+//! use serde_jce_derive::Jce;
+//!
+//! #[derive(Jce)]
+//! struct Struct {
+//!     #[jce(tag = 0, required, default)]
+//!     v0: i8,
+//! }
+//! ```
+//!
+
+mod attr;
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{
+    parse_macro_input, Data, DataEnum, DeriveInput, Expr, Fields, Lit, LitStr, Result, Type,
+};
+
+use attr::{serde_attrs, serde_keys, FieldAttr};
+
+#[proc_macro_derive(Jce, attributes(jce, serde))]
+pub fn derive_jce(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "Jce can only be derived for structs with named fields or fieldless enums",
+                ))
+            }
+        },
+        Data::Enum(data) => return expand_enum(input, data),
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "Jce can only be derived for structs with named fields or fieldless enums",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let shadow = format_ident!("__Jce{}", ident);
+
+    // fields of the shadow struct & helpers of default values
+    let mut errors: Option<syn::Error> = None;
+    let mut used = [None; 256];
+    let mut shadow_fields = Vec::new();
+    let mut default_fns = Vec::new();
+    let mut default_bounds = Vec::new();
+    let params: Vec<_> = input.generics.type_params().map(|p| &p.ident).collect();
+    for field in fields {
+        let attr = match FieldAttr::from_field(field) {
+            Ok(attr) => attr,
+            Err(err) => {
+                combine(&mut errors, err);
+                continue;
+            }
+        };
+        let name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+
+        let mut serde = Vec::new();
+        if let Some(tag) = attr.tag {
+            if let Some(prev) = used[tag as usize].replace(name) {
+                combine(
+                    &mut errors,
+                    syn::Error::new_spanned(
+                        field,
+                        format!("duplicate jce tag {}, already used by `{}`", tag, prev),
+                    ),
+                );
+            }
+            let rename = tag.to_string();
+            serde.push(quote!(#[serde(rename = #rename)]));
+        }
+        if attr.skip {
+            serde.push(quote!(#[serde(skip)]));
+        } else if let Some(expr) = &attr.default {
+            let func = format_ident!("__jce_default_{}", name.unraw());
+            let expr = match expr {
+                Expr::Lit(lit) if matches!(lit.lit, Lit::Str(_)) => {
+                    quote!(::core::convert::Into::into(#expr))
+                }
+                _ => quote!(#expr),
+            };
+            default_fns.push(quote! {
+                fn #func() -> #ty {
+                    #expr
+                }
+            });
+            let func = LitStr::new(&func.to_string(), Span::call_site());
+            serde.push(quote!(#[serde(default = #func)]));
+        } else if attr.required {
+            // serde treats a missing `Option` as `None` unless it has `deserialize_with`
+            let keys = serde_keys(&field.attrs)?;
+            if is_option(ty) && !keys.iter().any(|k| k == "with" || k == "deserialize_with") {
+                serde.push(quote!(#[serde(deserialize_with = "__jce_required")]));
+            }
+        } else {
+            serde.push(quote!(#[serde(default)]));
+            // serde doesn't infer `Default` bound of generic field
+            if mentions(ty.to_token_stream(), &params) {
+                let bound = quote!(#ty: _serde::Deserialize<'de> + ::core::default::Default);
+                let bound = LitStr::new(&bound.to_string(), Span::call_site());
+                serde.push(quote!(#[serde(bound(deserialize = #bound))]));
+                default_bounds.push(quote!(#ty: ::core::default::Default));
+            }
+        }
+        let kept = serde_attrs(&field.attrs);
+        shadow_fields.push(quote! {
+            #(#serde)*
+            #(#kept)*
+            #name: #ty
+        });
+    }
+    if let Some(err) = errors {
+        return Err(err);
+    }
+
+    // container attributes
+    let mut container = Vec::new();
+    let remote = LitStr::new(&ident.to_string(), Span::call_site());
+    container.push(quote!(#[serde(remote = #remote)]));
+    if container_bare(input)? {
+        container.push(quote!(#[serde(rename = "$serde_jce::bare")]));
+    } else if !serde_keys(&input.attrs)?.iter().any(|k| k == "rename") {
+        container.push(quote!(#[serde(rename = #remote)]));
+    }
+    let kept = serde_attrs(&input.attrs);
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // `T: Serialize` for every type parameter
+    let mut ser_generics = input.generics.clone();
+    for param in input.generics.type_params() {
+        let param = &param.ident;
+        ser_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#param: _serde::Serialize));
+    }
+    let (_, _, ser_where) = ser_generics.split_for_impl();
+
+    // `'de` outlives every lifetime & `T: Deserialize<'de>` for every type parameter
+    let mut de_generics = input.generics.clone();
+    let lifetimes: Vec<_> = input.generics.lifetimes().map(|l| &l.lifetime).collect();
+    de_generics
+        .params
+        .insert(0, syn::parse_quote!('de: #(#lifetimes)+*));
+    for param in input.generics.type_params() {
+        let param = &param.ident;
+        de_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#param: _serde::Deserialize<'de>));
+    }
+    for bound in default_bounds {
+        de_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#bound));
+    }
+    let (de_impl_generics, _, de_where) = de_generics.split_for_impl();
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(dead_code, non_snake_case, non_upper_case_globals, clippy::all)]
+        const _: () = {
+            use ::serde_jce::__private::serde as _serde;
+
+            #(#default_fns)*
+
+            fn __jce_required<'de, __D, __T>(deserializer: __D) -> ::core::result::Result<__T, __D::Error>
+            where
+                __D: _serde::Deserializer<'de>,
+                __T: _serde::Deserialize<'de>,
+            {
+                __T::deserialize(deserializer)
+            }
+
+            #[derive(_serde::Serialize, _serde::Deserialize)]
+            #[serde(crate = "::serde_jce::__private::serde")]
+            #(#container)*
+            #(#kept)*
+            #[allow(dead_code)]
+            struct #shadow #impl_generics #where_clause {
+                #(#shadow_fields,)*
+            }
+
+            impl #impl_generics _serde::Serialize for #ident #ty_generics #ser_where {
+                fn serialize<__S>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+                where
+                    __S: _serde::Serializer,
+                {
+                    #shadow::serialize(self, serializer)
+                }
+            }
+
+            impl #de_impl_generics _serde::Deserialize<'de> for #ident #ty_generics #de_where {
+                fn deserialize<__D>(deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+                where
+                    __D: _serde::Deserializer<'de>,
+                {
+                    #shadow::deserialize(deserializer)
+                }
+            }
+        };
+    })
+}
+
+/// Fieldless enum, encoded as its `i32` discriminant
+fn expand_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    if let Some(variant) = data.variants.iter().find(|v| !v.fields.is_empty()) {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "Jce can only be derived for enums without fields",
+        ));
+    }
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Jce can't be derived for generic enums",
+        ));
+    }
+
+    let ident = &input.ident;
+    let variants: Vec<_> = data.variants.iter().map(|v| &v.ident).collect();
+    let expecting = format!("discriminant of enum {}", ident);
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals, clippy::all)]
+        const _: () = {
+            use ::serde_jce::__private::serde as _serde;
+
+            impl _serde::Serialize for #ident {
+                fn serialize<__S>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+                where
+                    __S: _serde::Serializer,
+                {
+                    let discriminant = match self {
+                        #(#ident::#variants => #ident::#variants as i32,)*
+                    };
+                    serializer.serialize_i32(discriminant)
+                }
+            }
+
+            impl<'de> _serde::Deserialize<'de> for #ident {
+                fn deserialize<__D>(deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+                where
+                    __D: _serde::Deserializer<'de>,
+                {
+                    let discriminant = <i32 as _serde::Deserialize>::deserialize(deserializer)?;
+                    #(
+                        if discriminant == #ident::#variants as i32 {
+                            return ::core::result::Result::Ok(#ident::#variants);
+                        }
+                    )*
+                    ::core::result::Result::Err(_serde::de::Error::invalid_value(
+                        _serde::de::Unexpected::Signed(discriminant as i64),
+                        &#expecting,
+                    ))
+                }
+            }
+        };
+    })
+}
+
+fn combine(errors: &mut Option<syn::Error>, err: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(err),
+        None => *errors = Some(err),
+    }
+}
+
+/// Check if any of `params` appears in `tokens`
+fn mentions(tokens: TokenStream, params: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => params.iter().any(|p| **p == ident),
+        TokenTree::Group(group) => mentions(group.stream(), params),
+        _ => false,
+    })
+}
+
+/// Check if `ty` is `Option<T>`
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Check `#[jce(bare)]` of the container
+fn container_bare(input: &DeriveInput) -> Result<bool> {
+    let mut bare = false;
+    for jce in input.attrs.iter().filter(|a| a.path().is_ident("jce")) {
+        jce.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bare") {
+                bare = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown jce attribute"))
+            }
+        })?;
+    }
+    Ok(bare)
+}
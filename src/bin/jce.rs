@@ -0,0 +1,275 @@
+//! `jce` - decode, encode & inspect Jce bytes, requires the `cli` feature
+//!
+//! Run `jce help` for usage.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process;
+
+use serde_jce::dump::Dumper;
+use serde_jce::{JceParser, JsonHint, TypedValue, Value};
+
+const USAGE: &str = "\
+Usage: jce <command> [options] [file]
+
+Commands:
+    decode [file]       bytes -> tree, JSON or text notation
+    encode [file]       JSON or text notation -> bytes
+    validate [file]     check the bytes are well-formed, report the first error
+    diff <a> <b>        compare the values of two inputs of bytes
+
+A file of `-` or no file reads stdin.
+
+JSON has no wire types: `encode --from json` guesses them, so widths of
+integers, Float vs Double, Bytes and Maps of non-string keys are lost by
+`decode --to json | encode --from json`. Use `--to text` & `--from text`
+for a lossless round-trip.
+
+Options:
+    --from <format>     format of the input
+                        bytes: bin (default), hex, base64
+                        values: text (default), json
+    --to <format>       format of the output
+                        decode: tree (default), json, text
+                        encode: hex (default), bin, base64
+    --depth <n>         members deeper than `n` are skipped by the tree
+    --bare              bytes are the fields of a struct without StructBegin & StructEnd
+";
+
+struct Options {
+    command: String,
+    files: Vec<String>,
+    from: Option<String>,
+    to: Option<String>,
+    depth: Option<usize>,
+    bare: bool,
+}
+
+fn main() {
+    let opts = match parse_args(std::env::args().skip(1)) {
+        Ok(opts) => opts,
+        Err(msg) => {
+            eprintln!("error: {}\n\n{}", msg, USAGE);
+            process::exit(2);
+        }
+    };
+    let res = match opts.command.as_str() {
+        "decode" => decode(&opts),
+        "encode" => encode(&opts),
+        "validate" => validate(&opts),
+        "diff" => diff(&opts),
+        _ => {
+            print!("{}", USAGE);
+            Ok(true)
+        }
+    };
+    match res {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut opts = Options {
+        command: args.next().unwrap_or_else(|| "help".to_owned()),
+        files: Vec::new(),
+        from: None,
+        to: None,
+        depth: None,
+        bare: false,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("`{}` needs a value", arg));
+        match arg.as_str() {
+            "--from" => opts.from = Some(value()?),
+            "--to" => opts.to = Some(value()?),
+            "--depth" => {
+                let depth = value()?;
+                opts.depth = Some(
+                    depth
+                        .parse()
+                        .map_err(|_| format!("invalid depth `{}`", depth))?,
+                );
+            }
+            "--bare" => opts.bare = true,
+            "-" => opts.files.push(arg),
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+            _ => opts.files.push(arg),
+        }
+    }
+    let files = match opts.command.as_str() {
+        "decode" | "encode" | "validate" => 0..=1,
+        "diff" => 2..=2,
+        "help" => 0..=usize::MAX,
+        command => return Err(format!("unknown command `{}`", command)),
+    };
+    if !files.contains(&opts.files.len()) {
+        return Err(format!("wrong number of files for `{}`", opts.command));
+    }
+    Ok(opts)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// commands
+
+fn decode(opts: &Options) -> Result<bool, String> {
+    let bytes = read_bytes(opts, opts.files.first())?;
+    match opts.to.as_deref().unwrap_or("tree") {
+        "tree" => {
+            let mut dumper = Dumper::new();
+            if let Some(depth) = opts.depth {
+                dumper.max_depth(depth);
+            }
+            print!("{}", dumper.dump(&bytes));
+        }
+        "json" => {
+            let json = decode_bytes::<Value>(opts, &bytes)?.to_json();
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+        "text" => {
+            let val: TypedValue = decode_bytes(opts, &bytes)?;
+            println!("{}", val.to_text());
+        }
+        to => return Err(format!("unknown output format `{}`", to)),
+    }
+    Ok(true)
+}
+
+fn encode(opts: &Options) -> Result<bool, String> {
+    let input = String::from_utf8(read_file(opts.files.first())?).map_err(|e| e.to_string())?;
+    let bytes = match opts.from.as_deref().unwrap_or("text") {
+        "text" => serde_jce::to_bytes(&TypedValue::from_text(&input).map_err(|e| e.to_string())?),
+        "json" => {
+            let json = serde_json::from_str(&input).map_err(|e| e.to_string())?;
+            let val = Value::from_json(&json, &JsonHint::Any).map_err(|e| e.to_string())?;
+            serde_jce::to_bytes(&val)
+        }
+        from => return Err(format!("unknown input format `{}`", from)),
+    }
+    .map_err(|e| e.to_string())?;
+    let bytes = if opts.bare {
+        match (bytes.first(), bytes.last()) {
+            (Some(0x0a), Some(0x0b)) => bytes[1..bytes.len() - 1].to_vec(),
+            _ => return Err("`--bare` needs a struct of tag 0".to_owned()),
+        }
+    } else {
+        bytes
+    };
+
+    let mut stdout = io::stdout();
+    match opts.to.as_deref().unwrap_or("hex") {
+        "bin" => stdout.write_all(&bytes),
+        "hex" => writeln!(stdout, "{}", hex(&bytes)),
+        "base64" => writeln!(stdout, "{}", base64::encode(&bytes)),
+        to => return Err(format!("unknown output format `{}`", to)),
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn validate(opts: &Options) -> Result<bool, String> {
+    let bytes = read_bytes(opts, opts.files.first())?;
+    let mut parser = JceParser::from_bytes(&bytes);
+    while !parser.done() {
+        if let Err(err) = parser.ignore() {
+            println!("invalid: {}", err);
+            return Ok(false);
+        }
+    }
+    println!("ok: {} bytes", bytes.len());
+    Ok(true)
+}
+
+fn diff(opts: &Options) -> Result<bool, String> {
+    let a: Value = decode_bytes(opts, &read_bytes(opts, opts.files.first())?)?;
+    let b: Value = decode_bytes(opts, &read_bytes(opts, opts.files.get(1))?)?;
+    let changes = a.diff(&b);
+    for change in &changes {
+        println!("{}", change);
+    }
+    Ok(changes.is_empty())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// input
+
+fn read_file(file: Option<&String>) -> Result<Vec<u8>, String> {
+    match file.map(String::as_str) {
+        None | Some("-") => {
+            let mut buf = Vec::new();
+            io::stdin()
+                .read_to_end(&mut buf)
+                .map_err(|e| e.to_string())?;
+            Ok(buf)
+        }
+        Some(file) => fs::read(file).map_err(|e| format!("{}: {}", file, e)),
+    }
+}
+
+fn read_bytes(opts: &Options, file: Option<&String>) -> Result<Vec<u8>, String> {
+    let input = read_file(file)?;
+    match opts.from.as_deref().unwrap_or("bin") {
+        "bin" => Ok(input),
+        "hex" => {
+            let digits: Vec<u8> = input
+                .into_iter()
+                .filter(|c| !c.is_ascii_whitespace())
+                .collect();
+            if digits.len() % 2 != 0 {
+                return Err("invalid hex: odd number of digits".to_owned());
+            }
+            // `from_str_radix` would take a sign
+            if !digits.iter().all(u8::is_ascii_hexdigit) {
+                return Err("invalid hex".to_owned());
+            }
+            digits
+                .chunks(2)
+                .map(|pair| {
+                    std::str::from_utf8(pair)
+                        .ok()
+                        .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                        .ok_or_else(|| "invalid hex".to_owned())
+                })
+                .collect()
+        }
+        "base64" => {
+            let input: Vec<u8> = input
+                .into_iter()
+                .filter(|c| !c.is_ascii_whitespace())
+                .collect();
+            base64::decode(input).map_err(|e| format!("invalid base64: {}", e))
+        }
+        from => Err(format!("unknown input format `{}`", from)),
+    }
+}
+
+/// A single value, or a struct of the fields with `--bare`
+fn decode_bytes<T>(opts: &Options, bytes: &[u8]) -> Result<T, String>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let res = if opts.bare {
+        let mut wrapped = Vec::with_capacity(bytes.len() + 2);
+        wrapped.push(0x0a);
+        wrapped.extend_from_slice(bytes);
+        wrapped.push(0x0b);
+        serde_jce::from_bytes(&wrapped).map_err(|e| e.to_string())
+    } else {
+        serde_jce::from_bytes(bytes).map_err(|e| e.to_string())
+    };
+    res.map_err(|e| {
+        if !opts.bare && e.starts_with("TrailingBytes") {
+            format!("{}, try `--bare` for fields of a struct", e)
+        } else {
+            e
+        }
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
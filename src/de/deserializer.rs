@@ -1,19 +1,29 @@
+use std::fmt::{self, Display};
+use std::io;
 use std::marker::PhantomData;
 
-use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
-use serde::{forward_to_deserialize_any, serde_if_integer128, Deserialize};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    Unexpected, VariantAccess, Visitor,
+};
+use serde::{forward_to_deserialize_any, Deserialize};
 
-use crate::{Error, JceParser, JceType, Result};
+use super::parser::ANY;
+use super::read::{IoRead, Read, Reference, SliceRead};
+use crate::types::{BARE_STRUCT, RAW_VALUE};
+use crate::{Error, ErrorKind, JceParser, JceType, Result};
 
 /// A structure that deserializes Jce into Rust values.
-pub struct Deserializer<'de> {
-    parser: JceParser<'de>,
+pub struct Deserializer<'de, R = SliceRead<'de>> {
+    parser: JceParser<'de, R>,
+    path: Vec<Segment>,
 }
 
 impl<'de> Deserializer<'de> {
     pub fn from_bytes(bytes: &'de [u8]) -> Self {
         Self {
             parser: JceParser::from_bytes(bytes),
+            path: Vec::new(),
         }
     }
 
@@ -22,6 +32,91 @@ impl<'de> Deserializer<'de> {
     }
 }
 
+impl<'de, R> Deserializer<'de, IoRead<R>>
+where
+    R: io::Read,
+{
+    /// Deserialize values from a `std::io::Read`
+    ///
+    /// Bytes are pulled on demand, so values can be deserialized one after another from a stream.
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            parser: JceParser::from_reader(reader),
+            path: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.parser.into_inner()
+    }
+}
+
+impl<'de, R> Deserializer<'de, R>
+where
+    R: Read<'de>,
+{
+    /// Check that the input has been fully consumed
+    pub fn end(&mut self) -> Result<()> {
+        if self.parser.at_end()? {
+            Ok(())
+        } else {
+            Err(Error::from(ErrorKind::TrailingBytes).with_offset(self.parser.position()))
+        }
+    }
+
+    /// Deserialize the field of `tag` from `parser`, its errors are located in the field
+    pub(crate) fn field<T>(parser: JceParser<'de, R>, tag: u8) -> Result<T>
+    where
+        T: Deserialize<'de>,
+    {
+        let mut de = Self {
+            parser,
+            path: Vec::new(),
+        };
+        de.nested(Segment::Tag(tag), |de| T::deserialize(de))
+    }
+
+    /// Deserialize the value at `segment` of the path, locate its errors there if not located yet
+    fn nested<T>(&mut self, segment: Segment, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let start = self.parser.position();
+        self.path.push(segment);
+        let res = f(self).map_err(|e| e.with_offset(start).with_path(|| self.path()));
+        self.path.pop();
+        res
+    }
+
+    fn path(&self) -> String {
+        self.path.iter().map(|s| s.to_string()).collect()
+    }
+}
+
+/// A step of the path from the root value to an inner value
+pub(crate) enum Segment {
+    /// `.3` - field of tag 3
+    Tag(u8),
+    /// `[12]` - element 12 of a list, or the value of int key 12 of a map
+    Index(i64),
+    /// `.key` - the value of string key `key` of a map, quoted if it is not an identifier
+    Key(String),
+}
+
+impl Display for Segment {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Segment::Tag(tag) => write!(formatter, ".{}", tag),
+            Segment::Index(index) => write!(formatter, "[{}]", index),
+            Segment::Key(key) => {
+                let ident = !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+                if ident {
+                    write!(formatter, ".{}", key)
+                } else {
+                    write!(formatter, ".{:?}", key)
+                }
+            }
+        }
+    }
+}
+
 /// Deserialize an instance of type `T` from bytes of Jce.
 ///
 /// # Example
@@ -51,24 +146,64 @@ where
     T: Deserialize<'a>,
 {
     let mut deserializer = Deserializer::from_bytes(bytes);
-    let t = T::deserialize(&mut deserializer)?;
-    if deserializer.done() {
-        Ok(t)
-    } else {
-        Err(Error::TrailingBytes)
-    }
+    let t = T::deserialize(&mut deserializer).map_err(|e| e.with_offset(0))?;
+    deserializer.end()?;
+    Ok(t)
+}
+
+/// Deserialize an instance of type `T` from a `std::io::Read` of Jce.
+///
+/// The reader must end right after the value, otherwise `ErrorKind::TrailingBytes` is returned.
+/// Use `Deserializer::from_reader` to read several values from one stream.
+///
+/// A reader that ends in the middle of the value reports `ErrorKind::UnexpectedEof`.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(PartialEq, Debug, Deserialize)]
+/// struct Struct {
+///     #[serde(rename = "0")]
+///     v0: i8,
+///     #[serde(rename = "1")]
+///     v1: String,
+/// }
+/// let bytes = [0x0a, 0x00, 0x12, 0x16, 0x02, 0x61, 0x62, 0x0b];
+/// assert_eq!(
+///     serde_jce::from_reader::<_, Struct>(&bytes[..]).unwrap(),
+///     Struct {
+///         v0: 0x12,
+///         v1: "ab".to_owned(),
+///     }
+/// );
+/// ```
+///
+pub fn from_reader<R, T>(reader: R) -> Result<T>
+where
+    R: io::Read,
+    T: DeserializeOwned,
+{
+    let mut deserializer = Deserializer::from_reader(reader);
+    let t = T::deserialize(&mut deserializer).map_err(|e| e.with_offset(0))?;
+    deserializer.end()?;
+    Ok(t)
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
-impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+impl<'de, R> de::Deserializer<'de> for &mut Deserializer<'de, R>
+where
+    R: Read<'de>,
+{
     type Error = Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        let (_tag, tp) = self.parser.pick_head()?;
+        let (_tag, tp) = self.parser.peek_head()?;
         match tp {
             JceType::I8 => self.deserialize_i8(visitor),
             JceType::I16 => self.deserialize_i16(visitor),
@@ -76,15 +211,27 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
             JceType::I64 => self.deserialize_i64(visitor),
             JceType::F32 => self.deserialize_f32(visitor),
             JceType::F64 => self.deserialize_f64(visitor),
-            JceType::String1 => visitor.visit_borrowed_str(self.parser.str_small()?),
-            JceType::String4 => visitor.visit_borrowed_str(self.parser.str_big()?),
+            JceType::String1 => match self.parser.parse_str_small()? {
+                Reference::Borrowed(str) => visitor.visit_borrowed_str(str),
+                Reference::Copied(str) => visitor.visit_str(str),
+            },
+            JceType::String4 => match self.parser.parse_str_big()? {
+                Reference::Borrowed(str) => visitor.visit_borrowed_str(str),
+                Reference::Copied(str) => visitor.visit_str(str),
+            },
             JceType::Map => self.deserialize_map(visitor),
             JceType::List => self.deserialize_seq(visitor),
             JceType::StructBegin => {
                 self.parser.struct_begin()?;
                 visitor.visit_map(TagsAccess::new(self))
             }
-            JceType::StructEnd => todo!(),
+            JceType::StructEnd => {
+                let err = Error::from(ErrorKind::WrongType {
+                    expected: ANY,
+                    actual: JceType::StructEnd,
+                });
+                Err(err.with_offset(self.parser.position()))
+            }
             JceType::Zero => {
                 self.parser.zero()?;
                 visitor.visit_none()
@@ -128,13 +275,11 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         visitor.visit_i64(self.parser.i64()?)
     }
 
-    serde_if_integer128! {
-        fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
-        where
-            V: Visitor<'de>,
-        {
-            self.deserialize_i64(visitor)
-        }
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
     }
 
     fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value>
@@ -165,13 +310,11 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         self.deserialize_i64(visitor)
     }
 
-    serde_if_integer128! {
-        fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
-        where
-            V: Visitor<'de>,
-        {
-            self.deserialize_i128(visitor)
-        }
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i128(visitor)
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
@@ -192,7 +335,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        match self.parser.str()?.chars().nth(0) {
+        match self.parser.parse_str()?.chars().next() {
             Some(ch) => visitor.visit_char(ch),
             None => visitor.visit_char('\x00'),
         }
@@ -202,7 +345,10 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_borrowed_str(self.parser.str()?)
+        match self.parser.parse_str()? {
+            Reference::Borrowed(str) => visitor.visit_borrowed_str(str),
+            Reference::Copied(str) => visitor.visit_str(str),
+        }
     }
 
     fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
@@ -216,7 +362,10 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_borrowed_bytes(self.parser.bytes()?)
+        match self.parser.parse_bytes()? {
+            Reference::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Reference::Copied(bytes) => visitor.visit_bytes(bytes),
+        }
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
@@ -230,7 +379,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        match self.parser.pick_head()?.1 {
+        match self.parser.peek_head()?.1 {
             JceType::Zero => {
                 self.parser.zero()?;
                 visitor.visit_none()
@@ -254,11 +403,19 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         self.deserialize_unit(visitor)
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    /// `RawValue` captures the bytes of the value
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        self.deserialize_any(visitor)
+        if name == RAW_VALUE {
+            match self.parser.raw()? {
+                Reference::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+                Reference::Copied(bytes) => visitor.visit_bytes(bytes),
+            }
+        } else {
+            self.deserialize_any(visitor)
+        }
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
@@ -298,16 +455,30 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
 
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        self.parser.struct_begin()?;
-        let acc = TagsAccess::new_with_fields(self, fields)?;
-        visitor.visit_map(acc)
+        let start = self.parser.position();
+        let bare = name == BARE_STRUCT;
+        if !bare {
+            self.parser.struct_begin()?;
+        }
+        let acc = TagsAccess::new_with_fields(self, fields, bare)?;
+        match visitor.visit_map(acc) {
+            // name the struct of a missing required field
+            Err(err) => match *err.kind() {
+                ErrorKind::MissingField { tag, name: "" } => {
+                    let err = Error::from(ErrorKind::MissingField { tag, name });
+                    Err(err.with_offset(start))
+                }
+                _ => Err(err),
+            },
+            res => res,
+        }
     }
 
     fn deserialize_enum<V>(
@@ -319,7 +490,12 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        self.deserialize_any(visitor)
+        let start = self.parser.position();
+        let tp = self.parser.peek_head()?.1;
+        if tp == JceType::StructBegin {
+            self.parser.struct_begin()?;
+        }
+        visitor.visit_enum(Enum::new(self, tp, start))
     }
 
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
@@ -339,19 +515,29 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-struct Sequence<'a, 'de: 'a> {
-    de: &'a mut Deserializer<'de>,
+struct Sequence<'a, 'de: 'a, R> {
+    de: &'a mut Deserializer<'de, R>,
     cur: usize,
     size: usize,
+    /// Path segment of the current value of a map
+    key: Option<Segment>,
 }
 
-impl<'a, 'de> Sequence<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>, size: usize) -> Self {
-        Self { de, cur: 0, size }
+impl<'a, 'de, R> Sequence<'a, 'de, R> {
+    fn new(de: &'a mut Deserializer<'de, R>, size: usize) -> Self {
+        Self {
+            de,
+            cur: 0,
+            size,
+            key: None,
+        }
     }
 }
 
-impl<'de, 'a> MapAccess<'de> for Sequence<'a, 'de> {
+impl<'de, 'a, R> MapAccess<'de> for Sequence<'a, 'de, R>
+where
+    R: Read<'de>,
+{
     type Error = Error;
 
     fn size_hint(&self) -> Option<usize> {
@@ -365,6 +551,7 @@ impl<'de, 'a> MapAccess<'de> for Sequence<'a, 'de> {
         if self.cur == self.size {
             Ok(None)
         } else {
+            self.key = self.de.parser.peek_key();
             Ok(Some(seed.deserialize(&mut *self.de)?))
         }
     }
@@ -373,12 +560,19 @@ impl<'de, 'a> MapAccess<'de> for Sequence<'a, 'de> {
     where
         V: DeserializeSeed<'de>,
     {
+        let segment = match self.key.take() {
+            Some(key) => key,
+            None => Segment::Index(self.cur as i64),
+        };
         self.cur += 1;
-        seed.deserialize(&mut *self.de)
+        self.de.nested(segment, |de| seed.deserialize(de))
     }
 }
 
-impl<'de, 'a> SeqAccess<'de> for Sequence<'a, 'de> {
+impl<'de, 'a, R> SeqAccess<'de> for Sequence<'a, 'de, R>
+where
+    R: Read<'de>,
+{
     type Error = Error;
 
     fn size_hint(&self) -> Option<usize> {
@@ -392,18 +586,22 @@ impl<'de, 'a> SeqAccess<'de> for Sequence<'a, 'de> {
         if self.cur == self.size {
             Ok(None)
         } else {
+            let segment = Segment::Index(self.cur as i64);
             self.cur += 1;
-            Ok(Some(seed.deserialize(&mut *self.de)?))
+            Ok(Some(self.de.nested(segment, |de| seed.deserialize(de))?))
         }
     }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
-struct TagsAccess<'a, 'de: 'a> {
-    de: &'a mut Deserializer<'de>,
+struct TagsAccess<'a, 'de: 'a, R> {
+    de: &'a mut Deserializer<'de, R>,
+    /// Tag of the current field
+    tag: u8,
     tags: std::collections::HashSet<u8>,
     fields: Option<std::collections::HashSet<u8>>,
+    bare: bool,
 }
 
 struct StupidTagDeserializer<'de> {
@@ -411,7 +609,7 @@ struct StupidTagDeserializer<'de> {
     tag: u8,
 }
 
-impl<'de, 'a> de::Deserializer<'de> for &'a mut StupidTagDeserializer<'de> {
+impl<'de> de::Deserializer<'de> for &mut StupidTagDeserializer<'de> {
     type Error = Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
@@ -433,7 +631,7 @@ struct StupidStringDeserializer<'de> {
     tag: u8,
 }
 
-impl<'de, 'a> de::Deserializer<'de> for &'a mut StupidStringDeserializer<'de> {
+impl<'de> de::Deserializer<'de> for &mut StupidStringDeserializer<'de> {
     type Error = Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
@@ -450,53 +648,76 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut StupidStringDeserializer<'de> {
     }
 }
 
-impl<'de, 'a> TagsAccess<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>) -> Self {
+impl<'de, 'a, R> TagsAccess<'a, 'de, R>
+where
+    R: Read<'de>,
+{
+    fn new(de: &'a mut Deserializer<'de, R>) -> Self {
         Self {
             de,
+            tag: 0,
             tags: std::collections::HashSet::new(),
             fields: None,
+            bare: false,
         }
     }
 
     fn new_with_fields(
-        de: &'a mut Deserializer<'de>,
+        de: &'a mut Deserializer<'de, R>,
         fields: &'static [&'static str],
+        bare: bool,
     ) -> Result<Self> {
-        let mut set = std::collections::HashSet::new();
-        for &field in fields {
-            match field.parse() {
-                Ok(tag) => match set.insert(tag) {
-                    true => Ok(()),
-                    false => Err(Error::DuplicateFieldTagName),
-                },
-                Err(_) => Err(Error::ErrorFieldTag),
-            }?;
-        }
         Ok(Self {
             de,
+            tag: 0,
             tags: std::collections::HashSet::new(),
-            fields: Some(set),
+            fields: Some(field_tags(fields)?),
+            bare,
         })
     }
 
     /// Check if it is a terminator & if duplicated
+    ///
+    /// Bare struct ends at the end of input or before a `StructEnd` of its parent
     fn get_tag(&mut self) -> Result<Option<u8>> {
-        let (tag, tp) = self.de.parser.pick_head()?;
+        if self.bare && self.de.parser.at_end()? {
+            return Ok(None);
+        }
+        let (tag, tp) = self.de.parser.peek_head()?;
         if let JceType::StructEnd = tp {
-            self.de.parser.struct_end()?;
+            if !self.bare {
+                self.de.parser.struct_end()?;
+            }
             Ok(None)
+        } else if self.tags.insert(tag) {
+            self.tag = tag;
+            Ok(Some(tag))
         } else {
-            if self.tags.insert(tag) {
-                Ok(Some(tag))
-            } else {
-                Err(Error::DuplicateFieldTag)
-            }
+            let err = Error::from(ErrorKind::DuplicateFieldTag);
+            Err(err.with_offset(self.de.parser.position()))
         }
     }
 }
 
-impl<'de, 'a> MapAccess<'de> for TagsAccess<'a, 'de> {
+/// Tags of the fields of a struct, names of fields are their tags
+pub(super) fn field_tags(fields: &'static [&'static str]) -> Result<std::collections::HashSet<u8>> {
+    let mut set = std::collections::HashSet::new();
+    for &field in fields {
+        match field.parse() {
+            Ok(tag) => match set.insert(tag) {
+                true => Ok(()),
+                false => Err(Error::from(ErrorKind::DuplicateFieldTagName)),
+            },
+            Err(_) => Err(Error::from(ErrorKind::ErrorFieldTag)),
+        }?;
+    }
+    Ok(set)
+}
+
+impl<'de, 'a, R> MapAccess<'de> for TagsAccess<'a, 'de, R>
+where
+    R: Read<'de>,
+{
     type Error = Error;
 
     fn size_hint(&self) -> Option<usize> {
@@ -529,7 +750,7 @@ impl<'de, 'a> MapAccess<'de> for TagsAccess<'a, 'de> {
             if let Some(tag) = self.get_tag()? {
                 let mut des = StupidTagDeserializer {
                     phantom: PhantomData,
-                    tag: tag,
+                    tag,
                 };
                 Ok(Some(seed.deserialize(&mut des)?))
             } else {
@@ -542,6 +763,133 @@ impl<'de, 'a> MapAccess<'de> for TagsAccess<'a, 'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        seed.deserialize(&mut *self.de)
+        self.de
+            .nested(Segment::Tag(self.tag), |de| seed.deserialize(de))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Unit variant is an int of variant index,
+/// other variants are wrapped in a struct with variant index at `tag = 0` & data at `tag = 1`
+struct Enum<'a, 'de: 'a, R> {
+    de: &'a mut Deserializer<'de, R>,
+    /// Type of the enum value, `StructBegin` if it is wrapped
+    tp: JceType,
+    /// Offset of the enum value
+    start: usize,
+}
+
+impl<'a, 'de, R> Enum<'a, 'de, R>
+where
+    R: Read<'de>,
+{
+    fn new(de: &'a mut Deserializer<'de, R>, tp: JceType, start: usize) -> Self {
+        Self { de, tp, start }
+    }
+
+    fn wrapped(&self) -> bool {
+        self.tp == JceType::StructBegin
+    }
+
+    /// Check the data is at `tag = 1` of the wrapper struct
+    fn data_begin(&mut self) -> Result<()> {
+        if !self.wrapped() {
+            let err = Error::from(ErrorKind::WrongType {
+                expected: &[JceType::StructBegin],
+                actual: self.tp,
+            });
+            Err(err.with_offset(self.start))
+        } else if self.de.parser.peek_head()?.0 != 1 {
+            let err = Error::from(ErrorKind::ErrorFieldTag);
+            Err(err.with_offset(self.de.parser.position()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Swallow unknown fields & the terminator of the wrapper struct
+    fn end(&mut self) -> Result<()> {
+        if self.wrapped() {
+            while self.de.parser.peek_head()?.1 != JceType::StructEnd {
+                self.de.parser.ignore()?;
+            }
+            self.de.parser.struct_end()?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de, 'a, R> EnumAccess<'de> for Enum<'a, 'de, R>
+where
+    R: Read<'de>,
+{
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        if self.wrapped() && self.de.parser.peek_head()?.0 != 0 {
+            let err = Error::from(ErrorKind::ErrorFieldTag);
+            return Err(err.with_offset(self.de.parser.position()));
+        }
+        let index = self.de.parser.i32()?;
+        match u32::try_from(index) {
+            Ok(index) => {
+                let val = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(index))?;
+                Ok((val, self))
+            }
+            Err(_) => Err(de::Error::invalid_value(
+                Unexpected::Signed(index as i64),
+                &"variant index",
+            )),
+        }
+    }
+}
+
+impl<'de, 'a, R> VariantAccess<'de> for Enum<'a, 'de, R>
+where
+    R: Read<'de>,
+{
+    type Error = Error;
+
+    fn unit_variant(mut self) -> Result<()> {
+        self.end()
+    }
+
+    fn newtype_variant_seed<T>(mut self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.data_begin()?;
+        let val = self.de.nested(Segment::Tag(1), |de| seed.deserialize(de))?;
+        self.end()?;
+        Ok(val)
+    }
+
+    fn tuple_variant<V>(mut self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.data_begin()?;
+        let val = self.de.nested(Segment::Tag(1), |de| {
+            de::Deserializer::deserialize_seq(de, visitor)
+        })?;
+        self.end()?;
+        Ok(val)
+    }
+
+    fn struct_variant<V>(mut self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.data_begin()?;
+        let val = self.de.nested(Segment::Tag(1), |de| {
+            de::Deserializer::deserialize_struct(de, "", fields, visitor)
+        })?;
+        self.end()?;
+        Ok(val)
     }
 }
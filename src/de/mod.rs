@@ -1,5 +1,11 @@
 mod deserializer;
 mod parser;
+mod read;
+mod value;
 
-pub use deserializer::{from_bytes, Deserializer};
-pub use parser::JceParser;
+pub(crate) use deserializer::Segment;
+pub use deserializer::{from_bytes, from_reader, Deserializer};
+pub use parser::{Checkpoint, JceParser};
+pub(crate) use parser::{ANY, BYTES, F32, F64, I64, STR};
+pub use read::{IoRead, Read, SliceRead};
+pub use value::from_value;
@@ -1,4 +1,11 @@
-use crate::{Error, JceType, Result};
+use std::io;
+use std::marker::PhantomData;
+
+use serde::Deserialize;
+
+use super::deserializer::{Deserializer, Segment};
+use super::read::{IoRead, Read, Reference, SliceRead};
+use crate::{Error, ErrorKind, JceType, Result};
 
 /// Manually construct jce format
 ///
@@ -15,366 +22,524 @@ use crate::{Error, JceType, Result};
 /// assert_eq!(parser.done(), true);
 /// ```
 ///
+/// # Reader
+///
+/// `JceParser::from_reader` pulls bytes on demand from a `std::io::Read`.
+/// Strings & bytes can't be borrowed from a reader, they are returned as `String` & `Vec<u8>`.
+///
+/// ```
+/// use serde_jce::{JceParser, JceType};
+///
+/// let mut parser = JceParser::from_reader(&[0x06, 0x02, 0x61, 0x62][..]);
+/// assert_eq!(parser.pick_head(), Ok((0, JceType::String1)));
+/// assert_eq!(parser.str(), Ok("ab".to_owned()));
+/// assert_eq!(parser.done(), Ok(true));
+/// ```
+///
 /// # Error
 ///
-/// When `JceParser` returns `Error`, remaining bytes will change due to incorrect parsing
+/// Every parsing operation is atomic,
+/// when `JceParser` returns `Error`, nothing is consumed and the position is unchanged.
+/// So another interpretation of the value can be tried.
+///
+/// ```
+/// use serde_jce::JceParser;
+///
+/// let mut parser = JceParser::from_bytes(&[0x0d, 0x00, 0x00, 0x02, 0x61, 0x62]);
+/// assert!(parser.str().is_err());
+/// assert_eq!(parser.position(), 0);
+/// assert_eq!(parser.bytes(), Ok(&b"ab"[..]));
+/// ```
+///
+/// `Error::offset` is the byte offset where the error is found,
+/// e.g. the head of a value of `ErrorKind::WrongType`, or the start of a truncated value
 ///
-/// This means, the parsing operation is not atomic
+/// A reader that ends in the middle of a value reports `ErrorKind::UnexpectedEof` instead of `ErrorKind::NotEnoughtBytes`
 ///
-pub struct JceParser<'de> {
-    bytes: &'de [u8],
+/// # Checkpoint
+///
+/// A parser of bytes can go back to a `Checkpoint`, e.g. to parse a struct in another way.
+///
+/// This is not offered by a parser of a reader: it drops the bytes it has consumed,
+/// keeping them for a checkpoint that might be used later would buffer the whole stream.
+///
+/// ```
+/// use serde_jce::JceParser;
+///
+/// let mut parser = JceParser::from_bytes(&[0x00, 0x12, 0x11, 0x12, 0x34]);
+/// let checkpoint = parser.checkpoint();
+/// assert_eq!(parser.i8(), Ok(0x12));
+/// assert_eq!(parser.i16(), Ok(0x1234));
+/// assert_eq!(parser.rewind(checkpoint), Ok(()));
+/// assert_eq!(parser.i32(), Ok(0x12));
+/// ```
+///
+pub struct JceParser<'de, R = SliceRead<'de>> {
+    read: R,
+    phantom: PhantomData<&'de [u8]>,
+}
+
+/// Position of a `JceParser` to rewind to, see `JceParser::checkpoint`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    position: usize,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
 impl<'de> JceParser<'de> {
     pub fn from_bytes(bytes: &'de [u8]) -> Self {
-        Self { bytes }
+        Self::new(SliceRead::new(bytes))
     }
 
     pub fn done(&self) -> bool {
-        self.bytes.is_empty()
+        self.read.remaining().is_empty()
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.position(),
+        }
+    }
+
+    /// Go back to `checkpoint` of this parser
+    ///
+    /// A checkpoint beyond the end of the bytes, i.e. taken from another parser,
+    /// is `ErrorKind::InvalidCheckpoint` and the position is unchanged.
+    pub fn rewind(&mut self, checkpoint: Checkpoint) -> Result<()> {
+        if checkpoint.position > self.read.len() {
+            let err = Error::from(ErrorKind::InvalidCheckpoint);
+            return Err(err.with_offset(checkpoint.position));
+        }
+        self.read.rewind(checkpoint.position);
+        Ok(())
+    }
+}
+
+impl<'de, R> JceParser<'de, IoRead<R>>
+where
+    R: io::Read,
+{
+    pub fn from_reader(reader: R) -> Self {
+        Self::new(IoRead::new(reader))
+    }
+
+    /// Check if the reader is exhausted, may block waiting for more bytes
+    pub fn done(&mut self) -> Result<bool> {
+        self.read.at_end()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.read.into_inner()
+    }
+}
+
+impl<'de, R> JceParser<'de, R>
+where
+    R: Read<'de>,
+{
+    fn new(read: R) -> Self {
+        Self {
+            read,
+            phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn at_end(&mut self) -> Result<bool> {
+        self.read.at_end()
+    }
+
+    /// Number of bytes consumed so far
+    pub fn position(&self) -> usize {
+        self.read.position()
     }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 // pick parsing
 
+fn parse_head(bytes: &[u8]) -> Result<(u8, JceType)> {
+    match bytes.first() {
+        Some(head) => {
+            let tag = head >> 4;
+            let tp = TryFrom::try_from(head & 0x0f)?;
+            if tag != 0x0f {
+                Ok((tag, tp))
+            } else {
+                match bytes.get(1) {
+                    Some(tag) => Ok((*tag, tp)),
+                    None => Err(ErrorKind::NotEnoughtBytes.into()),
+                }
+            }
+        }
+        None => Err(ErrorKind::NotEnoughtBytes.into()),
+    }
+}
+
 impl<'de> JceParser<'de> {
     pub fn pick_tag(&self) -> Result<u8> {
         Ok(self.pick_head()?.0)
     }
 
     pub fn pick_type(&self) -> Result<JceType> {
-        match self.bytes.get(0) {
+        let res = match self.read.remaining().first() {
             Some(head) => TryFrom::try_from(head & 0x0f),
-            None => Err(Error::NotEnoughtBytes),
-        }
+            None => Err(ErrorKind::NotEnoughtBytes.into()),
+        };
+        res.map_err(|e: Error| e.with_offset(self.position()))
     }
 
     pub fn pick_head(&self) -> Result<(u8, JceType)> {
-        match self.bytes.get(0) {
-            Some(head) => {
-                let tag = head >> 4;
-                let tp = TryFrom::try_from(head & 0x0f)?;
-                if tag != 0x0f {
-                    Ok((tag, tp))
-                } else {
-                    match self.bytes.get(1) {
-                        Some(tag) => Ok((*tag, tp)),
-                        None => Err(Error::NotEnoughtBytes),
-                    }
-                }
-            }
-            None => Err(Error::NotEnoughtBytes),
-        }
+        parse_head(self.read.remaining()).map_err(|e| e.with_offset(self.position()))
     }
 }
 
-////////////////////////////////////////////////////////////////////////////////
-// get parsing
+impl<'de, R> JceParser<'de, IoRead<R>>
+where
+    R: io::Read,
+{
+    pub fn pick_tag(&mut self) -> Result<u8> {
+        Ok(self.pick_head()?.0)
+    }
 
-impl<'de> JceParser<'de> {
-    fn get_head(&mut self) -> Result<(u8, JceType)> {
-        let (tag, tp) = self.pick_head()?;
-        if tag < 15 {
-            self.bytes = &self.bytes[1..];
-        } else {
-            self.bytes = &self.bytes[2..];
-        }
+    pub fn pick_type(&mut self) -> Result<JceType> {
+        Ok(self.pick_head()?.1)
+    }
+
+    pub fn pick_head(&mut self) -> Result<(u8, JceType)> {
+        self.peek_head()
+    }
+}
+
+impl<'de, R> JceParser<'de, R>
+where
+    R: Read<'de>,
+{
+    pub(crate) fn peek_head(&mut self) -> Result<(u8, JceType)> {
+        let (tag, tp, _) = self.peek_head_at(0)?;
         Ok((tag, tp))
     }
 
-    fn get_bytes<T>(&mut self, len: T) -> Result<&'de [u8]>
-    where
-        T: TryInto<usize>,
-    {
-        let len: usize = match len.try_into() {
-            Ok(l) => Ok(l),
-            Err(_) => Err(Error::NotEnoughtBytes),
-        }?;
-        if len <= self.bytes.len() {
-            let (left, right) = self.bytes.split_at(len);
-            self.bytes = right;
-            Ok(left)
+    /// Peek the head `at` bytes ahead, return its tag, type & length
+    fn peek_head_at(&mut self, at: usize) -> Result<(u8, JceType, usize)> {
+        let len = if self.read.peek(at + 1)?[at] >> 4 != 0x0f {
+            1
         } else {
-            Err(Error::NotEnoughtBytes)
-        }
+            2
+        };
+        let offset = self.position() + at;
+        let (tag, tp) =
+            parse_head(&self.read.peek(at + len)?[at..]).map_err(|e| e.with_offset(offset))?;
+        Ok((tag, tp, len))
     }
 
-    fn get_bytes_fixed<const N: usize>(&mut self) -> Result<[u8; N]> {
-        if N <= self.bytes.len() {
-            let (left, right) = self.bytes.split_at(N);
-            self.bytes = right;
-            Ok(left.try_into().unwrap())
+    /// Peek the head `at` bytes ahead, its type must be one of `expected`
+    fn peek_head_of(
+        &mut self,
+        at: usize,
+        expected: &'static [JceType],
+    ) -> Result<(u8, JceType, usize)> {
+        let (tag, actual, len) = self.peek_head_at(at)?;
+        if expected.contains(&actual) {
+            Ok((tag, actual, len))
         } else {
-            Err(Error::NotEnoughtBytes)
+            let err = Error::from(ErrorKind::WrongType { expected, actual });
+            Err(err.with_offset(self.position() + at))
         }
     }
 
-    pub fn i8(&mut self) -> Result<i8> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(0),
-            JceType::I8 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i8::from_be_bytes(buf))
-            }
-            _ => Err(Error::WrongType),
+    /// Peek an int `at` bytes ahead, return it & its length with head
+    fn peek_int(&mut self, at: usize, expected: &'static [JceType]) -> Result<(i64, usize)> {
+        let (_, tp, head) = self.peek_head_of(at, expected)?;
+        let len = tp_len(tp);
+        if len == 0 {
+            return Ok((0, head));
         }
+        let bytes = &self.read.peek(at + head + len)?[at + head..];
+        // big endian & sign extended
+        let val = bytes
+            .iter()
+            .fold((bytes[0] as i8 >> 7) as i64, |acc, &b| acc << 8 | b as i64);
+        Ok((val, head + len))
     }
 
-    pub fn i16(&mut self) -> Result<i16> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(0),
-            JceType::I8 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i8::from_be_bytes(buf) as i16)
-            }
-            JceType::I16 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i16::from_be_bytes(buf))
-            }
-            _ => Err(Error::WrongType),
+    /// Peek the length of a bytes, map or list `at` bytes ahead, return it & its length with head
+    fn peek_length(&mut self, at: usize) -> Result<(usize, usize)> {
+        let (val, len) = self.peek_int(at, I32)?;
+        match usize::try_from(val) {
+            Ok(val) => Ok((val, len)),
+            Err(_) => Err(Error::from(ErrorKind::WrongLength).with_offset(self.position() + at)),
         }
     }
 
-    pub fn i32(&mut self) -> Result<i32> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(0),
-            JceType::I8 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i8::from_be_bytes(buf) as i32)
-            }
-            JceType::I16 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i16::from_be_bytes(buf) as i32)
-            }
-            JceType::I32 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i32::from_be_bytes(buf))
+    /// Peek an int or a short string without consuming it, to name a map value in a path
+    pub(crate) fn peek_key(&mut self) -> Option<Segment> {
+        match self.peek_head().ok()?.1 {
+            JceType::String1 => {
+                let (header, len) = self.peek_str_len(STR_SMALL).ok()?;
+                let bytes = &self.read.peek(header + len).ok()?[header..];
+                Some(Segment::Key(std::str::from_utf8(bytes).ok()?.to_owned()))
             }
-            _ => Err(Error::WrongType),
+            _ => Some(Segment::Index(self.peek_int(0, I64).ok()?.0)),
         }
     }
 
-    pub fn i64(&mut self) -> Result<i64> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(0),
-            JceType::I8 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i8::from_be_bytes(buf) as i64)
-            }
-            JceType::I16 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i16::from_be_bytes(buf) as i64)
-            }
-            JceType::I32 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i32::from_be_bytes(buf) as i64)
-            }
-            JceType::I64 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(i64::from_be_bytes(buf))
+    /// Peek the head & length of a string, return the length of them & the length of string
+    fn peek_str_len(&mut self, expected: &'static [JceType]) -> Result<(usize, usize)> {
+        let (_, tp, head) = self.peek_head_of(0, expected)?;
+        match tp {
+            JceType::String1 => Ok((head + 1, self.read.peek(head + 1)?[head] as usize)),
+            JceType::String4 => {
+                let bytes = self.read.peek(head + 4)?[head..].try_into().unwrap();
+                Ok((head + 4, u32::from_be_bytes(bytes) as usize))
             }
-            _ => Err(Error::WrongType),
+            _ => Ok((head, 0)),
         }
     }
+}
 
-    pub fn f32(&mut self) -> Result<f32> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(0.0),
-            JceType::F32 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(f32::from_be_bytes(buf))
-            }
-            _ => Err(Error::WrongType),
+////////////////////////////////////////////////////////////////////////////////
+// get parsing
+//
+// Everything of a value is peeked & checked before it is consumed,
+// so a failed operation consumes nothing
+
+pub(crate) const I8: &[JceType] = &[JceType::Zero, JceType::I8];
+pub(crate) const I16: &[JceType] = &[JceType::Zero, JceType::I8, JceType::I16];
+pub(crate) const I32: &[JceType] = &[JceType::Zero, JceType::I8, JceType::I16, JceType::I32];
+pub(crate) const I64: &[JceType] = &[
+    JceType::Zero,
+    JceType::I8,
+    JceType::I16,
+    JceType::I32,
+    JceType::I64,
+];
+pub(crate) const F32: &[JceType] = &[JceType::Zero, JceType::F32];
+pub(crate) const F64: &[JceType] = &[JceType::Zero, JceType::F32, JceType::F64];
+const STR_SMALL: &[JceType] = &[JceType::Zero, JceType::String1];
+const STR_BIG: &[JceType] = &[JceType::Zero, JceType::String4];
+pub(crate) const STR: &[JceType] = &[JceType::Zero, JceType::String1, JceType::String4];
+pub(crate) const BYTES: &[JceType] = &[JceType::Zero, JceType::Bytes];
+const BYTES_INNER: &[JceType] = &[JceType::I8];
+pub(crate) const MAP: &[JceType] = &[JceType::Zero, JceType::Map];
+pub(crate) const LIST: &[JceType] = &[JceType::Zero, JceType::List];
+pub(crate) const STRUCT_BEGIN: &[JceType] = &[JceType::StructBegin];
+const STRUCT_END: &[JceType] = &[JceType::StructEnd];
+pub(crate) const ZERO: &[JceType] = &[JceType::Zero];
+/// Every type but `StructEnd`, which can't start a value
+pub(crate) const ANY: &[JceType] = &[
+    JceType::I8,
+    JceType::I16,
+    JceType::I32,
+    JceType::I64,
+    JceType::F32,
+    JceType::F64,
+    JceType::String1,
+    JceType::String4,
+    JceType::Map,
+    JceType::List,
+    JceType::StructBegin,
+    JceType::Zero,
+    JceType::Bytes,
+];
+
+impl<'de, R> JceParser<'de, R>
+where
+    R: Read<'de>,
+{
+    fn get_str(&mut self, expected: &'static [JceType]) -> Result<Reference<'de, '_, str>> {
+        let (header, len) = self.peek_str_len(expected)?;
+        if std::str::from_utf8(&self.read.peek(header + len)?[header..]).is_err() {
+            let err = Error::from(ErrorKind::StringIsNotUtf8);
+            return Err(err.with_offset(self.position() + header));
+        }
+        self.read.discard(header);
+        // utf-8 is checked above
+        match self.read.read_slice(len)? {
+            Reference::Borrowed(buf) => Ok(Reference::Borrowed(std::str::from_utf8(buf).unwrap())),
+            Reference::Copied(buf) => Ok(Reference::Copied(std::str::from_utf8(buf).unwrap())),
         }
     }
 
-    pub fn f64(&mut self) -> Result<f64> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(0.0),
-            JceType::F32 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(f32::from_be_bytes(buf) as f64)
-            }
-            JceType::F64 => {
-                let buf = self.get_bytes_fixed()?;
-                Ok(f64::from_be_bytes(buf))
-            }
-            _ => Err(Error::WrongType),
-        }
+    pub(crate) fn parse_str_small(&mut self) -> Result<Reference<'de, '_, str>> {
+        self.get_str(STR_SMALL)
     }
 
-    pub fn str_small(&mut self) -> Result<&'de str> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(""),
-            JceType::String1 => {
-                let len = u8::from_be_bytes(self.get_bytes_fixed()?);
-                let buf = self.get_bytes(len)?;
-                match std::str::from_utf8(buf) {
-                    Ok(str) => Ok(str),
-                    Err(_) => Err(Error::StringIsNotUtf8),
-                }
-            }
-            _ => Err(Error::WrongType),
+    pub(crate) fn parse_str_big(&mut self) -> Result<Reference<'de, '_, str>> {
+        self.get_str(STR_BIG)
+    }
+
+    pub(crate) fn parse_str(&mut self) -> Result<Reference<'de, '_, str>> {
+        self.get_str(STR)
+    }
+
+    pub(crate) fn parse_bytes(&mut self) -> Result<Reference<'de, '_, [u8]>> {
+        let (_, tp, head) = self.peek_head_of(0, BYTES)?;
+        if tp == JceType::Zero {
+            self.read.discard(head);
+            return Ok(Reference::Borrowed(&[]));
         }
+        let (_, _, inner) = self.peek_head_of(head, BYTES_INNER)?;
+        let (len, size) = self.peek_length(head + inner)?;
+        let header = head + inner + size;
+        self.read.peek(header + len)?;
+        self.read.discard(header);
+        self.read.read_slice(len)
     }
 
-    pub fn str_big(&mut self) -> Result<&'de str> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(""),
-            JceType::String4 => {
-                let len = u32::from_be_bytes(self.get_bytes_fixed()?);
-                let buf = self.get_bytes(len)?;
-                match std::str::from_utf8(buf) {
-                    Ok(str) => Ok(str),
-                    Err(_) => Err(Error::StringIsNotUtf8),
-                }
+    fn get_int(&mut self, expected: &'static [JceType]) -> Result<i64> {
+        let (val, len) = self.peek_int(0, expected)?;
+        self.read.discard(len);
+        Ok(val)
+    }
+
+    pub fn i8(&mut self) -> Result<i8> {
+        Ok(self.get_int(I8)? as i8)
+    }
+
+    pub fn i16(&mut self) -> Result<i16> {
+        Ok(self.get_int(I16)? as i16)
+    }
+
+    pub fn i32(&mut self) -> Result<i32> {
+        Ok(self.get_int(I32)? as i32)
+    }
+
+    pub fn i64(&mut self) -> Result<i64> {
+        self.get_int(I64)
+    }
+
+    pub fn f32(&mut self) -> Result<f32> {
+        let (_, tp, head) = self.peek_head_of(0, F32)?;
+        let val = match tp {
+            JceType::F32 => {
+                f32::from_be_bytes(self.read.peek(head + 4)?[head..].try_into().unwrap())
             }
-            _ => Err(Error::WrongType),
-        }
+            _ => 0.0,
+        };
+        self.read.discard(head + tp_len(tp));
+        Ok(val)
     }
 
-    pub fn str(&mut self) -> Result<&'de str> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(""),
-            JceType::String1 => {
-                let len = u8::from_be_bytes(self.get_bytes_fixed()?);
-                let buf = self.get_bytes(len)?;
-                match std::str::from_utf8(buf) {
-                    Ok(str) => Ok(str),
-                    Err(_) => Err(Error::StringIsNotUtf8),
-                }
+    pub fn f64(&mut self) -> Result<f64> {
+        let (_, tp, head) = self.peek_head_of(0, F64)?;
+        let val = match tp {
+            JceType::F32 => {
+                f32::from_be_bytes(self.read.peek(head + 4)?[head..].try_into().unwrap()) as f64
             }
-            JceType::String4 => {
-                let len = u32::from_be_bytes(self.get_bytes_fixed()?);
-                let buf = self.get_bytes(len)?;
-                match std::str::from_utf8(buf) {
-                    Ok(str) => Ok(str),
-                    Err(_) => Err(Error::StringIsNotUtf8),
-                }
+            JceType::F64 => {
+                f64::from_be_bytes(self.read.peek(head + 8)?[head..].try_into().unwrap())
             }
-            _ => Err(Error::WrongType),
-        }
+            _ => 0.0,
+        };
+        self.read.discard(head + tp_len(tp));
+        Ok(val)
+    }
+
+    /// Length of map or list
+    fn get_length(&mut self, expected: &'static [JceType]) -> Result<usize> {
+        let (_, tp, head) = self.peek_head_of(0, expected)?;
+        let (val, len) = match tp {
+            JceType::Zero => (0, 0),
+            _ => self.peek_length(head)?,
+        };
+        self.read.discard(head + len);
+        Ok(val)
     }
 
     /// swallow headers & return the length of map
-    pub fn map<'a>(&'a mut self) -> Result<usize> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(0),
-            JceType::Map => match self.i32()?.try_into() {
-                Ok(val) => Ok(val),
-                Err(_) => Err(Error::WrongLength),
-            },
-            _ => Err(Error::WrongType),
-        }
+    pub fn map(&mut self) -> Result<usize> {
+        self.get_length(MAP)
     }
 
     /// swallow headers & return the length of list
-    pub fn list<'a>(&'a mut self) -> Result<usize> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(0),
-            JceType::List => match self.i32()?.try_into() {
-                Ok(val) => Ok(val),
-                Err(_) => Err(Error::WrongLength),
-            },
-            _ => Err(Error::WrongType),
-        }
+    pub fn list(&mut self) -> Result<usize> {
+        self.get_length(LIST)
+    }
+
+    fn get_head_of(&mut self, expected: &'static [JceType]) -> Result<()> {
+        let (_, _, head) = self.peek_head_of(0, expected)?;
+        self.read.discard(head);
+        Ok(())
     }
 
     /// Basically do nothing but swallow headers
-    pub fn struct_begin<'a>(&mut self) -> Result<()> {
-        match self.get_head()?.1 {
-            JceType::StructBegin => Ok(()),
-            _ => Err(Error::WrongType),
-        }
+    pub fn struct_begin(&mut self) -> Result<()> {
+        self.get_head_of(STRUCT_BEGIN)
     }
 
     /// Basically do nothing but swallow headers
-    pub fn struct_end<'a>(&mut self) -> Result<()> {
-        match self.get_head()?.1 {
-            JceType::StructEnd => Ok(()),
-            _ => Err(Error::WrongType),
-        }
+    pub fn struct_end(&mut self) -> Result<()> {
+        self.get_head_of(STRUCT_END)
     }
 
     pub fn zero(&mut self) -> Result<()> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(()),
-            _ => Err(Error::WrongType),
-        }
+        self.get_head_of(ZERO)
     }
 
-    pub fn bytes(&mut self) -> Result<&'de [u8]> {
-        match self.get_head()?.1 {
-            JceType::Zero => Ok(&[]),
-            JceType::Bytes => match self.get_head()?.1 {
-                JceType::I8 => {
-                    let len: usize = match self.i32()?.try_into() {
-                        Ok(val) => Ok(val),
-                        Err(_) => Err(Error::WrongLength),
-                    }?;
-                    self.get_bytes(len)
-                }
-                _ => Err(Error::WrongType),
-            },
-            _ => Err(Error::WrongType),
+    /// Skip a value, its bytes are not consumed if it fails
+    pub fn ignore(&mut self) -> Result<()> {
+        self.atomic(Self::skip)
+    }
+
+    /// Skip a value & return its bytes, head included
+    pub(crate) fn raw(&mut self) -> Result<Reference<'de, '_, [u8]>> {
+        self.peek_head_of(0, ANY)?;
+        let start = self.position();
+        // keep the skipped bytes to read them again
+        self.read.pin();
+        let res = self.ignore();
+        let len = self.position() - start;
+        self.read.rewind(start);
+        self.read.unpin();
+        res?;
+        self.read.read_slice(len)
+    }
+
+    /// Run `f`, go back to where it starts if it fails
+    fn atomic<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let start = self.position();
+        self.read.pin();
+        let res = f(self);
+        if res.is_err() {
+            self.read.rewind(start);
         }
+        self.read.unpin();
+        res
     }
 
-    pub fn ignore(&mut self) -> Result<()> {
-        match self.pick_type()? {
-            JceType::I8 => {
-                self.i8()?;
-            }
-            JceType::I16 => {
-                self.i16()?;
-            }
-            JceType::I32 => {
-                self.i32()?;
-            }
-            JceType::I64 => {
+    fn skip(&mut self) -> Result<()> {
+        match self.peek_head()?.1 {
+            JceType::I8 | JceType::I16 | JceType::I32 | JceType::I64 => {
                 self.i64()?;
             }
-            JceType::F32 => {
-                self.f32()?;
-            }
-            JceType::F64 => {
+            JceType::F32 | JceType::F64 => {
                 self.f64()?;
             }
-            JceType::String1 => {
-                self.str_small()?;
-            }
-            JceType::String4 => {
-                self.str_big()?;
+            JceType::String1 | JceType::String4 => {
+                let (header, len) = self.peek_str_len(STR)?;
+                self.read.peek(header + len)?;
+                self.read.discard(header + len);
             }
             JceType::Map => {
                 let len = self.map()?;
                 for _ in 0..len {
-                    self.ignore()?;
-                    self.ignore()?;
+                    self.skip()?;
+                    self.skip()?;
                 }
             }
             JceType::List => {
                 let len = self.list()?;
                 for _ in 0..len {
-                    self.ignore()?;
+                    self.skip()?;
                 }
             }
             JceType::StructBegin => {
                 self.struct_begin()?;
-                loop {
-                    match self.pick_type()? {
-                        JceType::StructEnd => {
-                            self.struct_end()?;
-                            break;
-                        }
-                        _ => {
-                            self.ignore()?;
-                        }
-                    }
+                while self.peek_head()?.1 != JceType::StructEnd {
+                    self.skip()?;
                 }
+                self.struct_end()?;
             }
             JceType::StructEnd => {
                 self.struct_end()?;
@@ -383,9 +548,134 @@ impl<'de> JceParser<'de> {
                 self.zero()?;
             }
             JceType::Bytes => {
-                self.bytes()?;
+                self.parse_bytes()?;
             }
         }
         Ok(())
     }
 }
+
+/// Length of the data of a number after its head
+fn tp_len(tp: JceType) -> usize {
+    match tp {
+        JceType::I8 => 1,
+        JceType::I16 => 2,
+        JceType::I32 | JceType::F32 => 4,
+        JceType::I64 | JceType::F64 => 8,
+        _ => 0,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// fields
+
+impl<'de, R> JceParser<'de, R>
+where
+    R: Read<'de>,
+{
+    /// Skip fields until the field of `tag`, return its type without consuming its head
+    ///
+    /// Return `None` at `StructEnd` or the end of input, which is not consumed
+    ///
+    /// ```
+    /// use serde_jce::{JceParser, JceType};
+    ///
+    /// let mut parser = JceParser::from_bytes(&[0x0a, 0x00, 0x12, 0x16, 0x01, 0x61, 0x2c, 0x0b]);
+    /// assert_eq!(parser.struct_begin(), Ok(()));
+    /// assert_eq!(parser.seek_tag(1), Ok(Some(JceType::String1)));
+    /// assert_eq!(parser.str(), Ok("a"));
+    /// assert_eq!(parser.seek_tag(3), Ok(None));
+    /// assert_eq!(parser.struct_end(), Ok(()));
+    /// ```
+    ///
+    pub fn seek_tag(&mut self, tag: u8) -> Result<Option<JceType>> {
+        self.atomic(|parser| loop {
+            if parser.at_end()? {
+                break Ok(None);
+            }
+            match parser.peek_head()? {
+                (_, JceType::StructEnd) => break Ok(None),
+                (t, tp) if t == tag => break Ok(Some(tp)),
+                _ => parser.ignore()?,
+            }
+        })
+    }
+
+    /// Skip fields until the field of `tag` & deserialize it, see `seek_tag`
+    ///
+    /// ```
+    /// use serde_jce::JceParser;
+    ///
+    /// let mut parser = JceParser::from_bytes(&[0x0a, 0x00, 0x12, 0x19, 0x0c, 0x2c, 0x0b]);
+    /// assert_eq!(parser.struct_begin(), Ok(()));
+    /// assert_eq!(parser.read_field::<Vec<i8>>(1), Ok(Some(vec![])));
+    /// assert_eq!(parser.read_field::<i64>(3), Ok(None));
+    /// ```
+    ///
+    pub fn read_field<T>(&mut self, tag: u8) -> Result<Option<T>>
+    where
+        T: Deserialize<'de>,
+    {
+        self.atomic(|parser| match parser.seek_tag(tag)? {
+            Some(_) => Deserializer::field(JceParser::new(&mut parser.read), tag).map(Some),
+            None => Ok(None),
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// borrowed strings & bytes
+
+impl<'de> JceParser<'de> {
+    pub fn str_small(&mut self) -> Result<&'de str> {
+        match self.parse_str_small()? {
+            Reference::Borrowed(str) => Ok(str),
+            Reference::Copied(_) => unreachable!(),
+        }
+    }
+
+    pub fn str_big(&mut self) -> Result<&'de str> {
+        match self.parse_str_big()? {
+            Reference::Borrowed(str) => Ok(str),
+            Reference::Copied(_) => unreachable!(),
+        }
+    }
+
+    pub fn str(&mut self) -> Result<&'de str> {
+        match self.parse_str()? {
+            Reference::Borrowed(str) => Ok(str),
+            Reference::Copied(_) => unreachable!(),
+        }
+    }
+
+    pub fn bytes(&mut self) -> Result<&'de [u8]> {
+        match self.parse_bytes()? {
+            Reference::Borrowed(bytes) => Ok(bytes),
+            Reference::Copied(_) => unreachable!(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// owned strings & bytes
+
+impl<'de, R> JceParser<'de, IoRead<R>>
+where
+    R: io::Read,
+{
+    pub fn str_small(&mut self) -> Result<String> {
+        Ok(self.parse_str_small()?.to_owned())
+    }
+
+    pub fn str_big(&mut self) -> Result<String> {
+        Ok(self.parse_str_big()?.to_owned())
+    }
+
+    pub fn str(&mut self) -> Result<String> {
+        Ok(self.parse_str()?.to_owned())
+    }
+
+    pub fn bytes(&mut self) -> Result<Vec<u8>> {
+        Ok(self.parse_bytes()?.to_vec())
+    }
+}
@@ -0,0 +1,286 @@
+use std::io;
+use std::ops::Deref;
+
+use crate::{Error, ErrorKind, Result};
+
+/// Source of bytes for `JceParser` and `Deserializer`
+///
+/// This trait is sealed and cannot be implemented outside of `serde_jce`.
+///
+/// * `SliceRead` - borrow from a `&[u8]`, strings & bytes can be zero-copy
+/// * `IoRead` - pull bytes on demand from a `std::io::Read`
+///
+pub trait Read<'de>: private::Sealed {
+    /// Look at the next `n` bytes without consuming them
+    #[doc(hidden)]
+    fn peek(&mut self, n: usize) -> Result<&[u8]>;
+
+    /// Consume `n` bytes, which must have been peeked before
+    #[doc(hidden)]
+    fn discard(&mut self, n: usize);
+
+    /// Consume `len` bytes, borrow them from input if possible
+    #[doc(hidden)]
+    fn read_slice(&mut self, len: usize) -> Result<Reference<'de, '_, [u8]>>;
+
+    /// Check if there are no more bytes
+    #[doc(hidden)]
+    fn at_end(&mut self) -> Result<bool>;
+
+    /// Number of bytes consumed so far
+    #[doc(hidden)]
+    fn position(&self) -> usize;
+
+    /// Go back to `position`, bytes since then must be kept by `pin`
+    ///
+    /// A position that isn't kept is clamped to the kept bytes.
+    #[doc(hidden)]
+    fn rewind(&mut self, position: usize);
+
+    /// Keep consumed bytes to rewind to until `unpin`, pins can be nested
+    #[doc(hidden)]
+    fn pin(&mut self);
+
+    #[doc(hidden)]
+    fn unpin(&mut self);
+}
+
+/// Bytes either borrowed from the input or copied into a buffer of the reader
+pub enum Reference<'b, 'c, T>
+where
+    T: ?Sized,
+{
+    Borrowed(&'b T),
+    Copied(&'c T),
+}
+
+impl<'b, 'c, T> Deref for Reference<'b, 'c, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match *self {
+            Reference::Borrowed(b) => b,
+            Reference::Copied(c) => c,
+        }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl<T> Sealed for &mut T where T: Sealed {}
+}
+
+impl<'de, T> Read<'de> for &mut T
+where
+    T: Read<'de>,
+{
+    fn peek(&mut self, n: usize) -> Result<&[u8]> {
+        (**self).peek(n)
+    }
+
+    fn discard(&mut self, n: usize) {
+        (**self).discard(n)
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<Reference<'de, '_, [u8]>> {
+        (**self).read_slice(len)
+    }
+
+    fn at_end(&mut self) -> Result<bool> {
+        (**self).at_end()
+    }
+
+    fn position(&self) -> usize {
+        (**self).position()
+    }
+
+    fn rewind(&mut self, position: usize) {
+        (**self).rewind(position)
+    }
+
+    fn pin(&mut self) {
+        (**self).pin()
+    }
+
+    fn unpin(&mut self) {
+        (**self).unpin()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Jce input source that reads from a slice of bytes
+pub struct SliceRead<'de> {
+    slice: &'de [u8],
+    index: usize,
+}
+
+impl<'de> SliceRead<'de> {
+    pub fn new(slice: &'de [u8]) -> Self {
+        Self { slice, index: 0 }
+    }
+
+    pub(crate) fn remaining(&self) -> &'de [u8] {
+        &self.slice[self.index..]
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.slice.len()
+    }
+}
+
+impl<'de> private::Sealed for SliceRead<'de> {}
+
+impl<'de> Read<'de> for SliceRead<'de> {
+    fn peek(&mut self, n: usize) -> Result<&[u8]> {
+        match self.remaining().get(..n) {
+            Some(bytes) => Ok(bytes),
+            None => Err(Error::from(ErrorKind::NotEnoughtBytes).with_offset(self.index)),
+        }
+    }
+
+    fn discard(&mut self, n: usize) {
+        self.index += n;
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<Reference<'de, '_, [u8]>> {
+        match self.remaining().get(..len) {
+            Some(bytes) => {
+                self.index += len;
+                Ok(Reference::Borrowed(bytes))
+            }
+            None => Err(Error::from(ErrorKind::NotEnoughtBytes).with_offset(self.index)),
+        }
+    }
+
+    fn at_end(&mut self) -> Result<bool> {
+        Ok(self.index == self.slice.len())
+    }
+
+    fn position(&self) -> usize {
+        self.index
+    }
+
+    fn rewind(&mut self, position: usize) {
+        self.index = position.min(self.slice.len());
+    }
+
+    fn pin(&mut self) {}
+
+    fn unpin(&mut self) {}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Jce input source that reads from a `std::io::Read`
+///
+/// Bytes are pulled on demand and never beyond the end of the current value,
+/// so several values can be read one after another from the same stream.
+///
+/// Every head is read by a separate small `read` call,
+/// wrap unbuffered sources such as `TcpStream` or `File` in a `std::io::BufReader`.
+///
+pub struct IoRead<R> {
+    reader: R,
+    /// Bytes pulled from `reader`, `buf[index..]` are not consumed yet
+    buf: Vec<u8>,
+    index: usize,
+    /// Offset of `buf[0]` in the stream
+    offset: usize,
+    /// Keep consumed bytes in `buf` to rewind to while pinned
+    pins: usize,
+}
+
+impl<R> IoRead<R>
+where
+    R: io::Read,
+{
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            index: 0,
+            offset: 0,
+            pins: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Pull bytes until there are `n` bytes not consumed
+    fn fill(&mut self, n: usize) -> Result<()> {
+        if self.pins == 0 && self.index != 0 {
+            self.buf.drain(..self.index);
+            self.offset += self.index;
+            self.index = 0;
+        }
+        let available = self.buf.len() - self.index;
+        if available < n {
+            // `take` avoids allocating a huge buffer for a bogus length up front
+            let rest = n - available;
+            let mut reader = io::Read::take(&mut self.reader, rest as u64);
+            match io::Read::read_to_end(&mut reader, &mut self.buf) {
+                Ok(read) if read == rest => {}
+                Ok(_) => {
+                    let err = Error::from(ErrorKind::UnexpectedEof);
+                    return Err(err.with_offset(self.position()));
+                }
+                Err(err) => return Err(Error::from(err).with_offset(self.position())),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R> private::Sealed for IoRead<R> where R: io::Read {}
+
+impl<'de, R> Read<'de> for IoRead<R>
+where
+    R: io::Read,
+{
+    fn peek(&mut self, n: usize) -> Result<&[u8]> {
+        self.fill(n)?;
+        Ok(&self.buf[self.index..self.index + n])
+    }
+
+    fn discard(&mut self, n: usize) {
+        self.index += n;
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<Reference<'de, '_, [u8]>> {
+        self.fill(len)?;
+        let start = self.index;
+        self.index += len;
+        Ok(Reference::Copied(&self.buf[start..self.index]))
+    }
+
+    fn at_end(&mut self) -> Result<bool> {
+        match self.peek(1) {
+            Ok(_) => Ok(false),
+            Err(err) if err.kind() == &ErrorKind::UnexpectedEof => Ok(true),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn position(&self) -> usize {
+        self.offset + self.index
+    }
+
+    fn rewind(&mut self, position: usize) {
+        self.index = position.saturating_sub(self.offset).min(self.buf.len());
+    }
+
+    fn pin(&mut self) {
+        self.pins += 1;
+    }
+
+    fn unpin(&mut self) {
+        self.pins -= 1;
+    }
+}
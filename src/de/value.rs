@@ -0,0 +1,617 @@
+use std::collections::{btree_map, BTreeMap as Map, HashSet};
+use std::vec;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    Unexpected, VariantAccess, Visitor,
+};
+
+use super::deserializer::{field_tags, Segment};
+use super::parser::{BYTES, F32, F64, I16, I32, I64, I8, LIST, MAP, STR, STRUCT_BEGIN, ZERO};
+use crate::types::RAW_VALUE;
+use crate::{Error, ErrorKind, JceType, Result, Value};
+
+/// Deserialize an instance of type `T` from a `Value`, without encoding it to bytes
+///
+/// `Value` is read as if it was decoded from bytes: fields of a struct are the tags of an `Object`,
+/// ints are checked to fit the type, `Zero` is `None`, `0` or empty.
+/// Unlike bytes, `Int(0)` is `Some(0)` for an `Option`.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_jce::Value;
+///
+/// #[derive(PartialEq, Debug, Deserialize)]
+/// struct Struct {
+///     #[serde(rename = "0")]
+///     v0: Option<i8>,
+///     #[serde(rename = "1")]
+///     v1: String,
+/// }
+/// let val = Value::Object([(0, Value::Int(0)), (1, Value::from("ab"))].into());
+/// assert_eq!(
+///     serde_jce::from_value::<Struct>(val).unwrap(),
+///     Struct {
+///         v0: Some(0),
+///         v1: "ab".to_owned(),
+///     }
+/// );
+/// ```
+///
+pub fn from_value<T>(value: Value) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(value)
+}
+
+/// An int whose narrowed type is one of `expected`
+fn int(val: Value, expected: &'static [JceType]) -> Result<i64> {
+    match val {
+        Value::Zero => Ok(0),
+        Value::Int(v) if expected.contains(&val.jce_type()) => Ok(v),
+        val => Err(val.wrong_type(expected)),
+    }
+}
+
+fn string(val: Value) -> Result<String> {
+    match val {
+        Value::Zero => Ok(String::new()),
+        Value::String(v) => Ok(v),
+        val => Err(val.wrong_type(STR)),
+    }
+}
+
+fn visit_list<'de, V>(list: Vec<Value>, visitor: V) -> Result<V::Value>
+where
+    V: Visitor<'de>,
+{
+    visitor.visit_seq(ListAccess {
+        iter: list.into_iter(),
+        index: 0,
+    })
+}
+
+fn visit_map<'de, V>(map: Map<Value, Value>, visitor: V) -> Result<V::Value>
+where
+    V: Visitor<'de>,
+{
+    visitor.visit_map(EntryAccess {
+        iter: map.into_iter(),
+        index: 0,
+        value: None,
+    })
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Zero => visitor.visit_none(),
+            Value::Int(v) => visitor.visit_i64(v),
+            Value::Float(v) => visitor.visit_f32(v),
+            Value::Double(v) => visitor.visit_f64(v),
+            Value::String(v) => visitor.visit_string(v),
+            Value::Bytes(v) => visitor.visit_byte_buf(v),
+            Value::List(v) => visit_list(v, visitor),
+            Value::Map(v) => visit_map(v, visitor),
+            Value::Object(v) => visitor.visit_map(ObjectAccess::new(v, None)),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bool(int(self, I8)? != 0)
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i8(int(self, I8)? as i8)
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i16(int(self, I16)? as i16)
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i32(int(self, I32)? as i32)
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i64(int(self, I64)?)
+    }
+
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i16(visitor)
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i32(visitor)
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i128(visitor)
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Zero => visitor.visit_f32(0.0),
+            Value::Float(v) => visitor.visit_f32(v),
+            val => Err(val.wrong_type(F32)),
+        }
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Zero => visitor.visit_f64(0.0),
+            Value::Float(v) => visitor.visit_f64(v as f64),
+            Value::Double(v) => visitor.visit_f64(v),
+            val => Err(val.wrong_type(F64)),
+        }
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match string(self)?.chars().next() {
+            Some(ch) => visitor.visit_char(ch),
+            None => visitor.visit_char('\x00'),
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(string(self)?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Zero => visitor.visit_byte_buf(Vec::new()),
+            Value::Bytes(v) => visitor.visit_byte_buf(v),
+            val => Err(val.wrong_type(BYTES)),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Zero => visitor.visit_none(),
+            val => visitor.visit_some(val),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Zero => visitor.visit_unit(),
+            val => Err(val.wrong_type(ZERO)),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    /// `RawValue` captures the value encoded to bytes
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if name == RAW_VALUE {
+            visitor.visit_byte_buf(crate::to_bytes(&self)?)
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Zero => visit_list(Vec::new(), visitor),
+            Value::List(v) => visit_list(v, visitor),
+            val => Err(val.wrong_type(LIST)),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Zero => visit_map(Map::new(), visitor),
+            Value::Map(v) => visit_map(v, visitor),
+            val => Err(val.wrong_type(MAP)),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let obj = match self {
+            Value::Object(v) => v,
+            val => return Err(val.wrong_type(STRUCT_BEGIN)),
+        };
+        let acc = ObjectAccess::new(obj, Some(field_tags(fields)?));
+        match visitor.visit_map(acc) {
+            // name the struct of a missing required field
+            Err(err) => match *err.kind() {
+                ErrorKind::MissingField { tag, name: "" } => {
+                    Err(ErrorKind::MissingField { tag, name }.into())
+                }
+                _ => Err(err),
+            },
+            res => res,
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(Enum { val: self })
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct ListAccess {
+    iter: vec::IntoIter<Value>,
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for ListAccess {
+    type Error = Error;
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(val) => {
+                let segment = Segment::Index(self.index as i64);
+                self.index += 1;
+                let res = seed.deserialize(val).map_err(|e| e.with_parent(segment))?;
+                Ok(Some(res))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+struct EntryAccess {
+    iter: btree_map::IntoIter<Value, Value>,
+    index: usize,
+    /// Path segment & the current value
+    value: Option<(Segment, Value)>,
+}
+
+impl<'de> MapAccess<'de> for EntryAccess {
+    type Error = Error;
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, val)) => {
+                let segment = match &key {
+                    Value::Int(k) => Segment::Index(*k),
+                    Value::String(k) => Segment::Key(k.clone()),
+                    _ => Segment::Index(self.index as i64),
+                };
+                self.index += 1;
+                self.value = Some((segment, val));
+                Ok(Some(seed.deserialize(key)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some((segment, val)) => seed.deserialize(val).map_err(|e| e.with_parent(segment)),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+}
+
+/// Fields of an `Object`, keys are tags, or names of tags if `fields` is known
+struct ObjectAccess {
+    iter: btree_map::IntoIter<u8, Value>,
+    fields: Option<HashSet<u8>>,
+    value: Option<(u8, Value)>,
+}
+
+impl ObjectAccess {
+    fn new(obj: Map<u8, Value>, fields: Option<HashSet<u8>>) -> Self {
+        Self {
+            iter: obj.into_iter(),
+            fields,
+            value: None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for ObjectAccess {
+    type Error = Error;
+
+    /// Unknown, so that `Value` tells an `Object` from a `Map`
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        for (tag, val) in self.iter.by_ref() {
+            match &self.fields {
+                Some(fields) if !fields.contains(&tag) => continue,
+                Some(_) => {
+                    self.value = Some((tag, val));
+                    let key = IntoDeserializer::<Error>::into_deserializer(tag.to_string());
+                    return Ok(Some(seed.deserialize(key)?));
+                }
+                None => {
+                    self.value = Some((tag, val));
+                    let key = IntoDeserializer::<Error>::into_deserializer(tag);
+                    return Ok(Some(seed.deserialize(key)?));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some((tag, val)) => seed
+                .deserialize(val)
+                .map_err(|e| e.with_parent(Segment::Tag(tag))),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Unit variant is an int of variant index,
+/// other variants are an `Object` with variant index at `tag = 0` & data at `tag = 1`
+struct Enum {
+    val: Value,
+}
+
+struct Variant {
+    /// Data at `tag = 1`, or the type of the unwrapped value
+    data: std::result::Result<Option<Value>, JceType>,
+}
+
+impl Variant {
+    fn data(self) -> Result<Value> {
+        match self.data {
+            Ok(Some(data)) => Ok(data),
+            Ok(None) => Err(ErrorKind::ErrorFieldTag.into()),
+            Err(actual) => Err(ErrorKind::WrongType {
+                expected: STRUCT_BEGIN,
+                actual,
+            }
+            .into()),
+        }
+    }
+}
+
+impl<'de> EnumAccess<'de> for Enum {
+    type Error = Error;
+    type Variant = Variant;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (index, data) = match self.val {
+            Value::Object(mut obj) => match obj.remove(&0) {
+                Some(index) => (index, Ok(obj.remove(&1))),
+                None => return Err(ErrorKind::ErrorFieldTag.into()),
+            },
+            val => {
+                let tp = val.jce_type();
+                (val, Err(tp))
+            }
+        };
+        let index = int(index, I32).map_err(|e| match data {
+            Ok(_) => e.with_parent(Segment::Tag(0)),
+            Err(_) => e,
+        })?;
+        match u32::try_from(index) {
+            Ok(index) => {
+                let val = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(index))?;
+                Ok((val, Variant { data }))
+            }
+            Err(_) => Err(de::Error::invalid_value(
+                Unexpected::Signed(index),
+                &"variant index",
+            )),
+        }
+    }
+}
+
+impl<'de> VariantAccess<'de> for Variant {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.data()?)
+            .map_err(|e| e.with_parent(Segment::Tag(1)))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.data()?, visitor)
+            .map_err(|e| e.with_parent(Segment::Tag(1)))
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(self.data()?, "", fields, visitor)
+            .map_err(|e| e.with_parent(Segment::Tag(1)))
+    }
+}
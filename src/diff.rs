@@ -0,0 +1,138 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::de::Segment;
+use crate::Value;
+
+/// A difference between two `Value`s, found by `Value::diff`
+///
+/// Displayed as lines of `- path: old` & `+ path: new` in text notation,
+/// the path of the root is `.`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change<'a> {
+    /// Path from the root, same as `Error::path`, e.g. `.1[0].name`
+    pub path: String,
+    pub kind: ChangeKind<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind<'a> {
+    /// Only in the other value
+    Added(&'a Value),
+    /// Only in this value
+    Removed(&'a Value),
+    /// In both, but not equal: `(old, new)`
+    Changed(&'a Value, &'a Value),
+}
+
+impl Value {
+    /// Differences from `self` to `other`, in order of paths
+    ///
+    /// Fields of `Object`s, entries of `Map`s & elements of `List`s are compared one by one,
+    /// every other pair of values is compared by `==`.
+    ///
+    /// ```
+    /// use serde_jce::{ChangeKind, Value};
+    ///
+    /// let old = Value::Object([(0, Value::Int(1)), (1, Value::List(vec![Value::Zero]))].into());
+    /// let new = Value::Object([(0, Value::Int(2)), (2, Value::from("a"))].into());
+    ///
+    /// let changes = old.diff(&new);
+    /// assert_eq!(changes.len(), 3);
+    /// assert_eq!(changes[0].path, ".0");
+    /// assert_eq!(changes[0].kind, ChangeKind::Changed(&Value::Int(1), &Value::Int(2)));
+    ///
+    /// let text: Vec<_> = changes.iter().map(|c| c.to_string()).collect();
+    /// assert_eq!(text, ["- .0: 1\n+ .0: 2", "- .1: [zero]", "+ .2: \"a\""]);
+    /// ```
+    ///
+    pub fn diff<'a>(&'a self, other: &'a Value) -> Vec<Change<'a>> {
+        let mut diff = Diff {
+            path: String::new(),
+            changes: Vec::new(),
+        };
+        diff.value(self, other);
+        diff.changes
+    }
+}
+
+struct Diff<'a> {
+    path: String,
+    changes: Vec<Change<'a>>,
+}
+
+impl<'a> Diff<'a> {
+    fn nested(&mut self, segment: impl fmt::Display, a: Option<&'a Value>, b: Option<&'a Value>) {
+        let len = self.path.len();
+        self.path.push_str(&segment.to_string());
+        match (a, b) {
+            (Some(a), Some(b)) => self.value(a, b),
+            (Some(a), None) => self.push(ChangeKind::Removed(a)),
+            (None, Some(b)) => self.push(ChangeKind::Added(b)),
+            (None, None) => {}
+        }
+        self.path.truncate(len);
+    }
+
+    fn value(&mut self, a: &'a Value, b: &'a Value) {
+        match (a, b) {
+            (Value::List(a), Value::List(b)) => {
+                for i in 0..std::cmp::max(a.len(), b.len()) {
+                    self.nested(Segment::Index(i as i64), a.get(i), b.get(i));
+                }
+            }
+            (Value::Map(a), Value::Map(b)) => {
+                let keys: BTreeSet<_> = a.keys().chain(b.keys()).collect();
+                for k in keys {
+                    self.nested(key_segment(k), a.get(k), b.get(k));
+                }
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                let tags: BTreeSet<_> = a.keys().chain(b.keys()).collect();
+                for tag in tags {
+                    self.nested(Segment::Tag(*tag), a.get(tag), b.get(tag));
+                }
+            }
+            (a, b) if a == b => {}
+            (a, b) => self.push(ChangeKind::Changed(a, b)),
+        }
+    }
+
+    fn push(&mut self, kind: ChangeKind<'a>) {
+        self.changes.push(Change {
+            path: self.path.clone(),
+            kind,
+        });
+    }
+}
+
+/// Int & string keys like paths of `Error`, other keys in text notation, e.g. `[1.5]`
+fn key_segment(key: &Value) -> String {
+    match key {
+        Value::Int(k) => Segment::Index(*k).to_string(),
+        Value::String(k) => Segment::Key(k.clone()).to_string(),
+        k => format!("[{}]", k.to_text()),
+    }
+}
+
+impl fmt::Display for Change<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "."
+        } else {
+            &self.path
+        };
+        match &self.kind {
+            ChangeKind::Added(v) => write!(f, "+ {}: {}", path, v.to_text()),
+            ChangeKind::Removed(v) => write!(f, "- {}: {}", path, v.to_text()),
+            ChangeKind::Changed(a, b) => write!(
+                f,
+                "- {}: {}\n+ {}: {}",
+                path,
+                a.to_text(),
+                path,
+                b.to_text()
+            ),
+        }
+    }
+}
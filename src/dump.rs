@@ -0,0 +1,227 @@
+//! Print Jce bytes as an indented tree, for logs & triaging captured traffic
+//!
+//! Every value is a line of its offset, tag, `JceType`, length & decoded value,
+//! members of lists, maps & structs are indented below it.
+//! Bytes & strings that are not UTF-8 are shown in hex.
+//!
+//! Malformed input doesn't fail the dump: everything before the error is printed,
+//! followed by an `error:` line with the error & its offset.
+//!
+//! # Example
+//!
+//! ```
+//! // `{ 0: 0x12, 1: "ab" }` followed by a truncated `[0x1234]`
+//! let bytes = [
+//!     0x0a, 0x00, 0x12, 0x16, 0x02, 0x61, 0x62, 0x0b, 0x19, 0x00, 0x01, 0x01, 0x12,
+//! ];
+//! assert_eq!(
+//!     serde_jce::dump::dump(&bytes),
+//!     "     0  0: StructBegin
+//!      1    0: I8 = 18
+//!      3    1: String1[2] = \"ab\"
+//!      7  StructEnd
+//!      8  1: List[1]
+//! error: NotEnoughtBytes, offset 11
+//! "
+//! );
+//! ```
+//!
+
+use std::fmt::Write;
+
+use crate::de::ANY;
+use crate::{Error, ErrorKind, JceParser, JceType, Result, SliceRead};
+
+/// Dump every value of `bytes` without a depth limit
+pub fn dump(bytes: &[u8]) -> String {
+    Dumper::new().dump(bytes)
+}
+
+/// Options of dumping
+///
+/// ```
+/// use serde_jce::dump::Dumper;
+///
+/// // `[[0x12]]`
+/// let bytes = [0x09, 0x00, 0x01, 0x09, 0x00, 0x01, 0x00, 0x12];
+/// assert_eq!(
+///     Dumper::new().max_depth(1).dump(&bytes),
+///     "     0  0: List[1]
+///      3    0: List[1]
+///      6      ... 2 bytes
+/// "
+/// );
+/// ```
+///
+pub struct Dumper {
+    max_depth: usize,
+}
+
+impl Default for Dumper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dumper {
+    pub fn new() -> Self {
+        Self {
+            max_depth: usize::MAX,
+        }
+    }
+
+    /// Members deeper than `depth` are skipped, top level values are at depth 0
+    pub fn max_depth(&mut self, depth: usize) -> &mut Self {
+        self.max_depth = depth;
+        self
+    }
+
+    pub fn dump(&self, bytes: &[u8]) -> String {
+        let mut dump = Dump {
+            parser: JceParser::from_bytes(bytes),
+            out: String::new(),
+            max_depth: self.max_depth,
+        };
+        while !dump.parser.done() {
+            if let Err(err) = dump.value(0) {
+                let _ = writeln!(dump.out, "error: {}", err);
+                break;
+            }
+        }
+        dump.out
+    }
+}
+
+struct Dump<'de> {
+    parser: JceParser<'de, SliceRead<'de>>,
+    out: String,
+    max_depth: usize,
+}
+
+impl<'de> Dump<'de> {
+    fn line(&mut self, offset: usize, depth: usize, text: &str) {
+        let _ = writeln!(self.out, "{:>6}  {}{}", offset, "  ".repeat(depth), text);
+    }
+
+    fn value(&mut self, depth: usize) -> Result<()> {
+        let offset = self.parser.position();
+        let (tag, tp) = self.parser.pick_head()?;
+        let head = format!("{}: {:?}", tag, tp);
+        let text = match tp {
+            JceType::I8 => format!("{} = {}", head, self.parser.i8()?),
+            JceType::I16 => format!("{} = {}", head, self.parser.i16()?),
+            JceType::I32 => format!("{} = {}", head, self.parser.i32()?),
+            JceType::I64 => format!("{} = {}", head, self.parser.i64()?),
+            JceType::F32 => format!("{} = {}", head, self.parser.f32()?),
+            JceType::F64 => format!("{} = {}", head, self.parser.f64()?),
+            JceType::String1 | JceType::String4 => self.string(head)?,
+            JceType::Bytes => {
+                let v = self.parser.bytes()?;
+                format!("{}[{}] = {}", head, v.len(), hex(v))
+            }
+            JceType::Zero => {
+                self.parser.zero()?;
+                head
+            }
+            JceType::List => {
+                let len = self.parser.list()?;
+                self.line(offset, depth, &format!("{}[{}]", head, len));
+                return self.members(depth + 1, len);
+            }
+            JceType::Map => {
+                let len = self.parser.map()?;
+                self.line(offset, depth, &format!("{}[{}]", head, len));
+                return self.members(depth + 1, len * 2);
+            }
+            JceType::StructBegin => {
+                self.parser.struct_begin()?;
+                self.line(offset, depth, &head);
+                return self.fields(depth);
+            }
+            JceType::StructEnd => {
+                let err: Error = ErrorKind::WrongType {
+                    expected: ANY,
+                    actual: tp,
+                }
+                .into();
+                return Err(err.with_offset(offset));
+            }
+        };
+        self.line(offset, depth, &text);
+        Ok(())
+    }
+
+    /// Strings that are not UTF-8 are shown in hex
+    fn string(&mut self, head: String) -> Result<String> {
+        match self.parser.str() {
+            Ok(v) => Ok(format!("{}[{}] = {:?}", head, v.len(), v)),
+            Err(err) if *err.kind() == ErrorKind::StringIsNotUtf8 => {
+                let raw = self.parser.raw()?;
+                let header = if raw[0] >> 4 == 0x0f { 2 } else { 1 };
+                let len = if raw[0] & 0x0f == JceType::String1 as u8 {
+                    1
+                } else {
+                    4
+                };
+                let v = &raw[header + len..];
+                Ok(format!("{}[{}] = {} (not UTF-8)", head, v.len(), hex(v)))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn members(&mut self, depth: usize, len: usize) -> Result<()> {
+        if depth > self.max_depth {
+            let offset = self.parser.position();
+            for _ in 0..len {
+                self.parser.ignore()?;
+            }
+            self.skipped(offset, depth);
+            return Ok(());
+        }
+        for _ in 0..len {
+            self.value(depth)?;
+        }
+        Ok(())
+    }
+
+    /// Fields & `StructEnd` of a struct at `depth`
+    fn fields(&mut self, depth: usize) -> Result<()> {
+        let start = self.parser.position();
+        loop {
+            let offset = self.parser.position();
+            if self.parser.pick_type()? == JceType::StructEnd {
+                if depth + 1 > self.max_depth {
+                    self.skipped(start, depth + 1);
+                }
+                self.parser.struct_end()?;
+                self.line(offset, depth, "StructEnd");
+                return Ok(());
+            }
+            if depth + 1 > self.max_depth {
+                self.parser.ignore()?;
+            } else {
+                self.value(depth + 1)?;
+            }
+        }
+    }
+
+    fn skipped(&mut self, offset: usize, depth: usize) {
+        let len = self.parser.position() - offset;
+        if len > 0 {
+            self.line(offset, depth, &format!("... {} bytes", len));
+        }
+    }
+}
+
+/// `12 34 56`
+fn hex(v: &[u8]) -> String {
+    let mut out = String::with_capacity(v.len() * 3);
+    for (i, b) in v.iter().enumerate() {
+        if i != 0 {
+            out.push(' ');
+        }
+        let _ = write!(out, "{:02x}", b);
+    }
+    out
+}
@@ -1,23 +1,80 @@
-use std;
 use std::fmt::{self, Display};
+use std::io;
 
 use serde::{de, ser};
 
+use crate::JceType;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Error of serializing, deserializing & parsing
+///
+/// Besides its `ErrorKind`, an error of deserializing may know where it happened:
+///
+/// * `offset` - byte offset within the input
+/// * `path` - tags, indices & map keys from the root value, e.g. `.3[12].key.1`
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_jce::{ErrorKind, JceType};
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Struct {
+///     #[serde(rename = "0")]
+///     v0: Vec<i8>,
+/// }
+///
+/// // `{ 0: [1, "a"] }`
+/// let bytes = [0x0a, 0x09, 0x00, 0x02, 0x00, 0x01, 0x06, 0x01, 0x61, 0x0b];
+/// let err = serde_jce::from_bytes::<Struct>(&bytes).unwrap_err();
+/// assert_eq!(
+///     err.kind(),
+///     &ErrorKind::WrongType {
+///         expected: &[JceType::Zero, JceType::I8],
+///         actual: JceType::String1,
+///     }
+/// );
+/// assert_eq!(err.offset(), Some(6));
+/// assert_eq!(err.path(), ".0[1]");
+/// ```
+///
 #[derive(Clone, Debug, PartialEq)]
-pub enum Error {
+pub struct Error {
+    kind: ErrorKind,
+    offset: Option<usize>,
+    path: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ErrorKind {
     Message(String),
     UnknownJceType,
 
     NotEnoughtBytes,
     TrailingBytes,
 
+    /// The `std::io::Read` source ended in the middle of a value
+    UnexpectedEof,
+    /// The `std::io::Read` source failed
+    Io(io::ErrorKind),
+
+    /// A `Checkpoint` is beyond the bytes of the `JceParser`, see `JceParser::rewind`
+    InvalidCheckpoint,
+
     ErrorFieldTag,
     DuplicateFieldTag,
     DuplicateFieldTagName,
+    /// A required field of struct `name` is missing
+    MissingField {
+        tag: u8,
+        name: &'static str,
+    },
 
-    WrongType,
+    /// Type of the value is not one of `expected`
+    WrongType {
+        expected: &'static [JceType],
+        actual: JceType,
+    },
     NeedLength,
     WrongLength,
     StringIsNotUtf8,
@@ -27,23 +84,167 @@ pub enum Error {
     BytesTooLong,
     SeqTooLong,
     MapTooLong,
+
+    /// `iVersion` of a Tars packet is not supported
+    UnknownVersion,
+
+    /// Length of a frame exceeds the maximum
+    FrameTooLong,
+    /// Length of a frame is less than its length head
+    FrameTooShort,
+    /// The `std::io::Read` source ended in the middle of a frame
+    FrameTruncated,
+
+    /// Syntax error of a `.jce` IDL file, `line` & `column` start from 1
+    Idl {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    /// Syntax error of the text notation of `Value`, `line` & `column` start from 1
+    Text {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    /// JSON doesn't match the `JsonHint`
+    Json(String),
+
+    /// Syntax error of a path of `Value::pointer`
+    InvalidPath(String),
+    /// Nothing at a path of `Value::pointer`, `Error::path` is the prefix of the path that failed
+    PathNotFound,
+}
+
+impl Error {
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn into_kind(self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Byte offset within the input, `None` if unknown
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Path of the value from the root value, empty for the root value itself or if unknown
+    ///
+    /// For errors of `Value::pointer`, the prefix of the pointer that failed, e.g. `/3/0`
+    pub fn path(&self) -> &str {
+        self.path.as_deref().unwrap_or("")
+    }
+
+    /// Set the offset if it is unknown, inner location wins
+    pub(crate) fn with_offset(mut self, offset: usize) -> Self {
+        if self.offset.is_none() {
+            self.offset = Some(offset);
+        }
+        self
+    }
+
+    /// Prepend `segment` to the path, for errors located from the inside out
+    pub(crate) fn with_parent(mut self, segment: impl Display) -> Self {
+        self.path = Some(format!("{}{}", segment, self.path()));
+        self
+    }
+
+    /// Set the path if it is unknown, inner location wins
+    pub(crate) fn with_path(mut self, path: impl FnOnce() -> String) -> Self {
+        if self.path.is_none() {
+            self.path = Some(path());
+        }
+        self
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            offset: None,
+            path: None,
+        }
+    }
 }
 
 impl ser::Error for Error {
     fn custom<T: Display>(msg: T) -> Self {
-        Error::Message(msg.to_string())
+        ErrorKind::Message(msg.to_string()).into()
     }
 }
 
 impl de::Error for Error {
     fn custom<T: Display>(msg: T) -> Self {
-        Error::Message(msg.to_string())
+        ErrorKind::Message(msg.to_string()).into()
+    }
+
+    /// Name of field is its tag, the name of struct is filled by `Deserializer`
+    fn missing_field(field: &'static str) -> Self {
+        match field.parse() {
+            Ok(tag) => ErrorKind::MissingField { tag, name: "" }.into(),
+            Err(_) => ErrorKind::Message(format!("missing field `{}`", field)).into(),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof => ErrorKind::UnexpectedEof.into(),
+            kind => ErrorKind::Io(kind).into(),
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Message(msg) | ErrorKind::Json(msg) => formatter.write_str(msg),
+            ErrorKind::MissingField { tag, name } => {
+                write!(formatter, "missing field of tag {} in `{}`", tag, name)
+            }
+            ErrorKind::WrongType { expected, actual } => {
+                write!(formatter, "expected type ")?;
+                for (i, tp) in expected.iter().enumerate() {
+                    if i != 0 {
+                        write!(formatter, " or ")?;
+                    }
+                    write!(formatter, "{:?}", tp)?;
+                }
+                write!(formatter, ", found {:?}", actual)
+            }
+            ErrorKind::Idl {
+                line,
+                column,
+                message,
+            }
+            | ErrorKind::Text {
+                line,
+                column,
+                message,
+            } => write!(formatter, "{}:{}: {}", line, column, message),
+            ErrorKind::InvalidPath(msg) => write!(formatter, "invalid path: {}", msg),
+            kind => fmt::Debug::fmt(kind, formatter),
+        }
     }
 }
 
+/// `expected type Zero or I8, found String1 at .0[1], offset 6`
 impl Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self, formatter)
+        Display::fmt(&self.kind, formatter)?;
+        if !self.path().is_empty() {
+            write!(formatter, " at {}", self.path())?;
+        }
+        if let Some(offset) = self.offset {
+            write!(formatter, ", offset {}", offset)?;
+        }
+        Ok(())
     }
 }
 
@@ -0,0 +1,121 @@
+/// A parsed `.jce` file
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Idl {
+    /// Files of `#include`, not resolved
+    pub includes: Vec<String>,
+    pub modules: Vec<Module>,
+}
+
+/// `module Name { ... };`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module {
+    pub name: String,
+    pub items: Vec<Item>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Item {
+    Struct(Struct),
+    Enum(Enum),
+    Const(Const),
+    Key(Key),
+    Interface(Interface),
+}
+
+/// `struct Name { 0 require int a; 1 optional string b = "b"; };`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Struct {
+    pub name: String,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub tag: u8,
+    /// `require` or `optional`
+    pub required: bool,
+    pub ty: Type,
+    pub name: String,
+    pub default: Option<Literal>,
+}
+
+/// `enum Name { A, B = 5 };`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Enum {
+    pub name: String,
+    pub variants: Vec<EnumVariant>,
+}
+
+/// Value is resolved, a variant without `= N` is the previous value plus one
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumVariant {
+    pub name: String,
+    pub value: i32,
+}
+
+/// `const int NAME = 1;`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Const {
+    pub ty: Type,
+    pub name: String,
+    pub value: Literal,
+}
+
+/// `key[Struct, a, b];`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Key {
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
+/// `interface Name { int call(int a, out string b); };`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Interface {
+    pub name: String,
+    pub operations: Vec<Operation>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Operation {
+    pub name: String,
+    /// `None` for `void`
+    pub ret: Option<Type>,
+    pub params: Vec<Param>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+    /// `out` parameter
+    pub out: bool,
+    pub ty: Type,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Type {
+    Bool,
+    Byte,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+    UnsignedByte,
+    UnsignedShort,
+    UnsignedInt,
+    Vector(Box<Type>),
+    Map(Box<Type>, Box<Type>),
+    /// A struct or an enum, `Module::Name` or `Name`
+    Custom(Vec<String>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    /// An enum variant, `Module::Enum::VARIANT` or `VARIANT`
+    Ident(Vec<String>),
+}
@@ -0,0 +1,352 @@
+use std::fmt::Write;
+
+use super::ast::*;
+
+/// Generate Rust code of `idl`
+///
+/// Every `module` becomes a Rust module in snake case, field names are converted to snake case,
+/// and enum variants to camel case.
+///
+/// * `struct` - `#[derive(Jce)]` struct, `require` fields are `#[jce(required)]`,
+///   `optional` fields are `Option<T>` unless they have a default value
+/// * `enum` - fieldless enum with the same discriminants
+/// * `const` - `pub const`
+/// * `key` - a `key()` method of the struct, returning the key fields as a tuple
+/// * `interface` - nothing is generated
+///
+pub fn generate(idl: &Idl) -> String {
+    let mut out = String::new();
+    out.push_str("// Generated by serde_jce::idl, do not edit\n");
+    for module in &idl.modules {
+        Codegen { idl, module }.module(&mut out);
+    }
+    out
+}
+
+struct Codegen<'a> {
+    idl: &'a Idl,
+    module: &'a Module,
+}
+
+impl<'a> Codegen<'a> {
+    fn module(&self, out: &mut String) {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "pub mod {} {{", module_name(&self.module.name));
+        let mut first = true;
+        for item in &self.module.items {
+            let mut code = String::new();
+            match item {
+                Item::Struct(s) => self.jce_struct(&mut code, s),
+                Item::Enum(e) => self.jce_enum(&mut code, e),
+                Item::Const(c) => self.jce_const(&mut code, c),
+                Item::Key(k) => self.key(&mut code, k),
+                Item::Interface(_) => {}
+            }
+            if code.is_empty() {
+                continue;
+            }
+            if !first {
+                out.push('\n');
+            }
+            first = false;
+            for line in code.lines() {
+                if line.is_empty() {
+                    out.push('\n');
+                } else {
+                    let _ = writeln!(out, "    {}", line);
+                }
+            }
+        }
+        let _ = writeln!(out, "}}");
+    }
+
+    fn jce_struct(&self, out: &mut String, s: &Struct) {
+        let _ = writeln!(out, "#[derive(Clone, Debug, PartialEq, ::serde_jce::Jce)]");
+        let _ = writeln!(out, "pub struct {} {{", s.name);
+        for field in &s.fields {
+            let (ty, default) = self.field_ty(field);
+
+            let mut jce = format!("tag = {}", field.tag);
+            if field.required {
+                jce.push_str(", required");
+            } else if let Some(default) = &default {
+                let _ = write!(jce, ", default = {}", default);
+            }
+            let _ = writeln!(out, "    #[jce({})]", jce);
+            if field.ty == Type::Vector(Box::new(Type::Byte)) {
+                let _ = writeln!(
+                    out,
+                    "    #[serde(with = \"::serde_jce::__private::serde_bytes\")]"
+                );
+            }
+            if !field.required && default.is_none() {
+                let _ = writeln!(
+                    out,
+                    "    #[serde(skip_serializing_if = \"::core::option::Option::is_none\")]"
+                );
+            }
+            let _ = writeln!(out, "    pub {}: {},", field_name(&field.name), ty);
+        }
+        let _ = writeln!(out, "}}");
+    }
+
+    fn jce_enum(&self, out: &mut String, e: &Enum) {
+        let _ = writeln!(
+            out,
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::serde_jce::Jce)]"
+        );
+        let _ = writeln!(out, "pub enum {} {{", e.name);
+        for variant in &e.variants {
+            let _ = writeln!(
+                out,
+                "    {} = {},",
+                variant_name(&variant.name),
+                variant.value
+            );
+        }
+        let _ = writeln!(out, "}}");
+    }
+
+    fn jce_const(&self, out: &mut String, c: &Const) {
+        let ty = match c.ty {
+            Type::String => "&str".to_owned(),
+            _ => self.ty(&c.ty),
+        };
+        let value = match (&c.ty, &c.value) {
+            (Type::String, Literal::String(v)) => format!("{:?}", v),
+            (ty, value) => match self.literal(ty, value) {
+                Some(value) => value,
+                None => return,
+            },
+        };
+        let _ = writeln!(out, "pub const {}: {} = {};", c.name, ty, value);
+    }
+
+    fn key(&self, out: &mut String, k: &Key) {
+        let s = self.module.items.iter().find_map(|item| match item {
+            Item::Struct(s) if s.name == k.name => Some(s),
+            _ => None,
+        });
+        let fields: Vec<_> = match s {
+            Some(s) => k
+                .fields
+                .iter()
+                .filter_map(|name| s.fields.iter().find(|f| &f.name == name))
+                .collect(),
+            None => return,
+        };
+        let types: Vec<_> = fields
+            .iter()
+            .map(|f| format!("&{}", self.field_ty(f).0))
+            .collect();
+        let values: Vec<_> = fields
+            .iter()
+            .map(|f| format!("&self.{}", field_name(&f.name)))
+            .collect();
+        let trailing = if fields.len() == 1 { "," } else { "" };
+        let _ = writeln!(out, "impl {} {{", k.name);
+        let _ = writeln!(
+            out,
+            "    pub fn key(&self) -> ({}{}) {{",
+            types.join(", "),
+            trailing
+        );
+        let _ = writeln!(out, "        ({}{})", values.join(", "), trailing);
+        let _ = writeln!(out, "    }}");
+        let _ = writeln!(out, "}}");
+    }
+
+    /// Type of field & its default value
+    ///
+    /// `optional` field without a default value is `Option<T>`
+    fn field_ty(&self, field: &Field) -> (String, Option<String>) {
+        let ty = self.ty(&field.ty);
+        if field.required {
+            return (ty, None);
+        }
+        match field
+            .default
+            .as_ref()
+            .and_then(|v| self.literal(&field.ty, v))
+        {
+            Some(default) => (ty, Some(default)),
+            None => (format!("::core::option::Option<{}>", ty), None),
+        }
+    }
+
+    fn ty(&self, ty: &Type) -> String {
+        match ty {
+            Type::Bool => "bool".to_owned(),
+            Type::Byte => "i8".to_owned(),
+            Type::Short => "i16".to_owned(),
+            Type::Int => "i32".to_owned(),
+            Type::Long => "i64".to_owned(),
+            Type::Float => "f32".to_owned(),
+            Type::Double => "f64".to_owned(),
+            Type::String => "::std::string::String".to_owned(),
+            Type::UnsignedByte => "u8".to_owned(),
+            Type::UnsignedShort => "u16".to_owned(),
+            Type::UnsignedInt => "u32".to_owned(),
+            Type::Vector(item) if **item == Type::Byte => "::std::vec::Vec<u8>".to_owned(),
+            Type::Vector(item) => format!("::std::vec::Vec<{}>", self.inner_ty(item)),
+            Type::Map(key, value) => format!(
+                "::std::collections::BTreeMap<{}, {}>",
+                self.inner_ty(key),
+                self.inner_ty(value)
+            ),
+            Type::Custom(path) => self.path(path),
+        }
+    }
+
+    /// Nested `vector<byte>` can't use `#[serde(with)]`
+    fn inner_ty(&self, ty: &Type) -> String {
+        match ty {
+            Type::Vector(item) if **item == Type::Byte => {
+                "::serde_jce::__private::serde_bytes::ByteBuf".to_owned()
+            }
+            _ => self.ty(ty),
+        }
+    }
+
+    /// `Name` or `Module::Name`
+    fn path(&self, path: &[String]) -> String {
+        match path {
+            [name] => name.clone(),
+            [module, name] if *module == self.module.name => name.clone(),
+            [module, rest @ ..] => format!("super::{}::{}", module_name(module), rest.join("::")),
+            [] => unreachable!(),
+        }
+    }
+
+    /// Find the enum of a custom type
+    fn find_enum(&self, path: &[String]) -> Option<&'a Enum> {
+        let (module, name) = match path {
+            [name] => (self.module.name.as_str(), name),
+            [module, name] => (module.as_str(), name),
+            _ => return None,
+        };
+        self.idl
+            .modules
+            .iter()
+            .filter(|m| m.name == module)
+            .flat_map(|m| m.items.iter())
+            .find_map(|item| match item {
+                Item::Enum(e) if &e.name == name => Some(e),
+                _ => None,
+            })
+    }
+
+    /// Rust expression of `value`, `None` if it doesn't fit `ty`
+    fn literal(&self, ty: &Type, value: &Literal) -> Option<String> {
+        let code = match (ty, value) {
+            (Type::Bool, Literal::Bool(v)) => v.to_string(),
+            (Type::Bool, Literal::Int(v)) => (*v != 0).to_string(),
+            (
+                Type::Byte
+                | Type::Short
+                | Type::Int
+                | Type::Long
+                | Type::UnsignedByte
+                | Type::UnsignedShort
+                | Type::UnsignedInt,
+                Literal::Int(v),
+            ) => v.to_string(),
+            (Type::Float | Type::Double, Literal::Int(v)) => format!("{}.0", v),
+            (Type::Float | Type::Double, Literal::Float(v)) => format!("{:?}", v),
+            (Type::String, Literal::String(v)) => format!("{:?}", v),
+            (Type::Custom(path), value) => {
+                let e = self.find_enum(path)?;
+                let variant = match value {
+                    Literal::Ident(name) => {
+                        e.variants.iter().find(|v| Some(&v.name) == name.last())
+                    }
+                    Literal::Int(i) => e.variants.iter().find(|v| v.value as i64 == *i),
+                    _ => None,
+                }?;
+                format!("{}::{}", self.path(path), variant_name(&variant.name))
+            }
+            _ => return None,
+        };
+        Some(code)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// naming
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// `sHelloWorld` -> `s_hello_world`, `HTTPCode` -> `http_code`
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let boundary = match prev {
+                Some(p) if p.is_ascii_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_ascii_uppercase() => {
+                    matches!(next, Some(n) if n.is_ascii_lowercase())
+                }
+                _ => false,
+            };
+            if boundary && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// `RED_COLOR` -> `RedColor`, `Red` -> `Red`
+fn camel_case(name: &str) -> String {
+    if name.chars().any(|c| c.is_ascii_lowercase()) && !name.contains('_') {
+        let mut chars = name.chars();
+        return match chars.next() {
+            Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+            None => String::new(),
+        };
+    }
+    name.split('_')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let mut chars = s.chars();
+            match chars.next() {
+                Some(first) => {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+                }
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn escape(name: String) -> String {
+    match name.as_str() {
+        "self" | "super" | "crate" | "Self" => name + "_",
+        _ if KEYWORDS.contains(&name.as_str()) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+fn module_name(name: &str) -> String {
+    escape(snake_case(name))
+}
+
+fn field_name(name: &str) -> String {
+    escape(snake_case(name))
+}
+
+fn variant_name(name: &str) -> String {
+    escape(camel_case(name))
+}
@@ -0,0 +1,97 @@
+//! Parser & code generator of Jce IDL (`.jce` files)
+//!
+//! `parse` turns a `.jce` file into an `Idl` AST, `generate` turns the AST into Rust code.
+//! The generated structs use `#[derive(Jce)]`, which requires the `derive` feature of `serde_jce`.
+//!
+//! # build.rs
+//!
+//! `compile` does both for a set of files, call it from `build.rs` so the generated types always match the IDL.
+//!
+//! ```no_run
+//! // main of build.rs
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("hello.rs");
+//! serde_jce::idl::compile(&["jce/hello.jce"], out).unwrap();
+//! println!("cargo:rerun-if-changed=jce/hello.jce");
+//! ```
+//!
+//! ```ignore
+//! // src/lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/hello.rs"));
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! let idl = serde_jce::idl::parse(
+//!     r#"
+//!     module Test {
+//!         enum Color { RED, GREEN = 5 };
+//!         struct Hello {
+//!             0 require string sName;
+//!             1 optional int iAge;
+//!             2 optional Color eColor = GREEN;
+//!         };
+//!     };
+//!     "#,
+//! )
+//! .unwrap();
+//! let code = serde_jce::idl::generate(&idl);
+//! assert!(code.contains("pub mod test {"));
+//! assert!(code.contains("    Green = 5,"));
+//! assert!(code.contains("pub s_name: ::std::string::String,"));
+//! assert!(code.contains("pub i_age: ::core::option::Option<i32>,"));
+//! assert!(code.contains("#[jce(tag = 2, default = Color::Green)]"));
+//! ```
+//!
+
+mod ast;
+mod codegen;
+mod parser;
+
+use std::path::Path;
+
+pub use ast::*;
+pub use codegen::generate;
+pub use parser::parse;
+
+use crate::{Error, ErrorKind, Result};
+
+/// Parse `.jce` files & write the generated Rust code into `output`
+///
+/// `#include` is not followed, list every file in `inputs`.
+/// Modules with the same name in different files are merged.
+///
+/// The `message` of `ErrorKind::Idl` starts with the path of the file.
+///
+pub fn compile<P, Q>(inputs: &[P], output: Q) -> Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let mut merged = Idl::default();
+    for input in inputs {
+        let input = input.as_ref();
+        let idl = parse(&std::fs::read_to_string(input)?).map_err(|err| match err.into_kind() {
+            ErrorKind::Idl {
+                line,
+                column,
+                message,
+            } => ErrorKind::Idl {
+                line,
+                column,
+                message: format!("{}: {}", input.display(), message),
+            }
+            .into(),
+            kind => Error::from(kind),
+        })?;
+        merged.includes.extend(idl.includes);
+        for module in idl.modules {
+            match merged.modules.iter_mut().find(|m| m.name == module.name) {
+                Some(m) => m.items.extend(module.items),
+                None => merged.modules.push(module),
+            }
+        }
+    }
+    std::fs::write(output, generate(&merged))?;
+    Ok(())
+}
@@ -0,0 +1,574 @@
+use super::ast::*;
+use crate::{ErrorKind, Result};
+
+////////////////////////////////////////////////////////////////////////////////
+// lexer
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Punct(char),
+    Eof,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    tok: Tok,
+    line: usize,
+    column: usize,
+}
+
+fn error<T>(line: usize, column: usize, message: impl Into<String>) -> Result<T> {
+    Err(ErrorKind::Idl {
+        line,
+        column,
+        message: message.into(),
+    }
+    .into())
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            chars: src.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Skip whitespaces & comments
+    fn skip(&mut self) -> Result<()> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('/') => {
+                    let (line, column) = (self.line, self.column);
+                    let mut ahead = self.chars.clone();
+                    ahead.next();
+                    match ahead.next() {
+                        Some('/') => while !matches!(self.bump(), Some('\n') | None) {},
+                        Some('*') => {
+                            self.bump();
+                            self.bump();
+                            loop {
+                                match self.bump() {
+                                    Some('*') if self.peek() == Some('/') => {
+                                        self.bump();
+                                        break;
+                                    }
+                                    Some(_) => {}
+                                    None => return error(line, column, "unterminated comment"),
+                                }
+                            }
+                        }
+                        _ => return Ok(()),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn tokenize(mut self) -> Result<Vec<Token>> {
+        let mut tokens = Vec::new();
+        loop {
+            self.skip()?;
+            let (line, column) = (self.line, self.column);
+            let tok = match self.peek() {
+                None => {
+                    tokens.push(Token {
+                        tok: Tok::Eof,
+                        line,
+                        column,
+                    });
+                    return Ok(tokens);
+                }
+                Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                    let mut ident = String::new();
+                    while let Some(c) = self
+                        .peek()
+                        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+                    {
+                        ident.push(c);
+                        self.bump();
+                    }
+                    Tok::Ident(ident)
+                }
+                Some(c) if c.is_ascii_digit() => self.number(line, column)?,
+                Some('"') => {
+                    self.bump();
+                    let mut str = String::new();
+                    loop {
+                        match self.bump() {
+                            Some('"') => break,
+                            Some('\\') => match self.bump() {
+                                Some('n') => str.push('\n'),
+                                Some('t') => str.push('\t'),
+                                Some('r') => str.push('\r'),
+                                Some('0') => str.push('\0'),
+                                Some(c) => str.push(c),
+                                None => return error(line, column, "unterminated string"),
+                            },
+                            Some(c) => str.push(c),
+                            None => return error(line, column, "unterminated string"),
+                        }
+                    }
+                    Tok::Str(str)
+                }
+                Some(c) if "{}()<>[];,=:-#".contains(c) => {
+                    self.bump();
+                    Tok::Punct(c)
+                }
+                Some(c) => return error(line, column, format!("unexpected character `{}`", c)),
+            };
+            tokens.push(Token { tok, line, column });
+        }
+    }
+
+    fn number(&mut self, line: usize, column: usize) -> Result<Tok> {
+        let mut text = String::new();
+        while let Some(c) = self
+            .peek()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '.')
+        {
+            text.push(c);
+            self.bump();
+            // exponent sign
+            if (c == 'e' || c == 'E') && !is_hex(&text) {
+                if let Some(sign) = self.peek().filter(|c| *c == '+' || *c == '-') {
+                    text.push(sign);
+                    self.bump();
+                }
+            }
+        }
+        let tok = if is_hex(&text) {
+            // `f` & `F` are digits of hex
+            let hex = text[2..].trim_end_matches(|c| matches!(c, 'l' | 'L' | 'u' | 'U'));
+            i64::from_str_radix(hex, 16).ok().map(Tok::Int)
+        } else {
+            let trimmed = text.trim_end_matches(|c| matches!(c, 'f' | 'F' | 'l' | 'L' | 'u' | 'U'));
+            if trimmed.contains(|c| matches!(c, '.' | 'e' | 'E')) {
+                trimmed.parse().ok().map(Tok::Float)
+            } else {
+                trimmed.parse().ok().map(Tok::Int)
+            }
+        };
+        match tok {
+            Some(tok) => Ok(tok),
+            None => error(line, column, format!("invalid number `{}`", text)),
+        }
+    }
+}
+
+fn is_hex(number: &str) -> bool {
+    number.starts_with("0x") || number.starts_with("0X")
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// parser
+
+/// Parse the content of a `.jce` file
+///
+/// # Example
+///
+/// ```
+/// use serde_jce::idl::{parse, Item, Type};
+///
+/// let idl = parse("module Test { struct Hello { 0 require string sName; }; };").unwrap();
+/// match &idl.modules[0].items[0] {
+///     Item::Struct(hello) => assert_eq!(hello.fields[0].ty, Type::String),
+///     _ => unreachable!(),
+/// }
+/// ```
+///
+pub fn parse(src: &str) -> Result<Idl> {
+    let tokens = Lexer::new(src).tokenize()?;
+    let mut parser = Parser { tokens, index: 0 };
+    parser.idl()
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Tok {
+        &self.tokens[self.index].tok
+    }
+
+    fn peek_nth(&self, n: usize) -> &Tok {
+        let index = std::cmp::min(self.index + n, self.tokens.len() - 1);
+        &self.tokens[index].tok
+    }
+
+    fn next(&mut self) -> Tok {
+        let tok = self.tokens[self.index].tok.clone();
+        if tok != Tok::Eof {
+            self.index += 1;
+        }
+        tok
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T> {
+        let token = &self.tokens[self.index];
+        error(token.line, token.column, message)
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T> {
+        let found = match self.peek() {
+            Tok::Ident(ident) => format!("`{}`", ident),
+            Tok::Int(v) => format!("`{}`", v),
+            Tok::Float(v) => format!("`{}`", v),
+            Tok::Str(v) => format!("{:?}", v),
+            Tok::Punct(c) => format!("`{}`", c),
+            Tok::Eof => "end of file".to_owned(),
+        };
+        self.error(format!("expected {}, found {}", expected, found))
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        *self.peek() == Tok::Punct(c)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Tok::Ident(ident) if ident == keyword)
+    }
+
+    fn punct(&mut self, c: char) -> Result<()> {
+        if self.is_punct(c) {
+            self.next();
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{}`", c))
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.is_keyword(keyword) {
+            self.next();
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{}`", keyword))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.peek() {
+            Tok::Ident(ident) => {
+                let ident = ident.clone();
+                self.next();
+                Ok(ident)
+            }
+            _ => self.unexpected("identifier"),
+        }
+    }
+
+    /// `A::B::C`
+    fn path(&mut self) -> Result<Vec<String>> {
+        let mut path = vec![self.ident()?];
+        while self.is_punct(':') && *self.peek_nth(1) == Tok::Punct(':') {
+            self.next();
+            self.next();
+            path.push(self.ident()?);
+        }
+        Ok(path)
+    }
+
+    /// `;` after `}` is optional
+    fn semicolon(&mut self) {
+        if self.is_punct(';') {
+            self.next();
+        }
+    }
+
+    fn idl(&mut self) -> Result<Idl> {
+        let mut idl = Idl::default();
+        loop {
+            match self.peek() {
+                Tok::Eof => return Ok(idl),
+                Tok::Punct('#') => {
+                    self.next();
+                    self.keyword("include")?;
+                    match self.peek() {
+                        Tok::Str(file) => {
+                            idl.includes.push(file.clone());
+                            self.next();
+                        }
+                        _ => return self.unexpected("file name"),
+                    }
+                }
+                _ => idl.modules.push(self.module()?),
+            }
+        }
+    }
+
+    fn module(&mut self) -> Result<Module> {
+        self.keyword("module")?;
+        let name = self.ident()?;
+        self.punct('{')?;
+        let mut items = Vec::new();
+        while !self.is_punct('}') {
+            let item = match self.peek() {
+                Tok::Ident(ident) if ident == "struct" => Item::Struct(self.jce_struct()?),
+                Tok::Ident(ident) if ident == "enum" => Item::Enum(self.jce_enum()?),
+                Tok::Ident(ident) if ident == "const" => Item::Const(self.jce_const()?),
+                Tok::Ident(ident) if ident == "key" => Item::Key(self.key()?),
+                Tok::Ident(ident) if ident == "interface" => Item::Interface(self.interface()?),
+                _ => return self.unexpected("`struct`, `enum`, `const`, `key` or `interface`"),
+            };
+            items.push(item);
+        }
+        self.punct('}')?;
+        self.semicolon();
+        Ok(Module { name, items })
+    }
+
+    fn jce_struct(&mut self) -> Result<Struct> {
+        self.keyword("struct")?;
+        let name = self.ident()?;
+        self.punct('{')?;
+        let mut fields: Vec<Field> = Vec::new();
+        while !self.is_punct('}') {
+            let tag = match self.peek() {
+                Tok::Int(tag) => match u8::try_from(*tag) {
+                    Ok(tag) => tag,
+                    Err(_) => return self.error("tag must be in 0..=255"),
+                },
+                _ => return self.unexpected("tag"),
+            };
+            if let Some(field) = fields.iter().find(|f| f.tag == tag) {
+                return self.error(format!(
+                    "duplicate tag {}, already used by `{}`",
+                    tag, field.name
+                ));
+            }
+            self.next();
+            let required = match self.peek() {
+                Tok::Ident(ident) if ident == "require" => true,
+                Tok::Ident(ident) if ident == "optional" => false,
+                _ => return self.unexpected("`require` or `optional`"),
+            };
+            self.next();
+            let ty = self.ty()?;
+            let name = self.ident()?;
+            let default = if self.is_punct('=') {
+                self.next();
+                Some(self.literal()?)
+            } else {
+                None
+            };
+            self.punct(';')?;
+            fields.push(Field {
+                tag,
+                required,
+                ty,
+                name,
+                default,
+            });
+        }
+        self.punct('}')?;
+        self.semicolon();
+        Ok(Struct { name, fields })
+    }
+
+    fn jce_enum(&mut self) -> Result<Enum> {
+        self.keyword("enum")?;
+        let name = self.ident()?;
+        self.punct('{')?;
+        let mut variants: Vec<EnumVariant> = Vec::new();
+        while !self.is_punct('}') {
+            let name = self.ident()?;
+            let value = if self.is_punct('=') {
+                self.next();
+                self.enum_value(&variants)?
+            } else {
+                match variants.last() {
+                    Some(prev) => match prev.value.checked_add(1) {
+                        Some(v) => v,
+                        None => return self.error("enum value must be an i32"),
+                    },
+                    None => 0,
+                }
+            };
+            variants.push(EnumVariant { name, value });
+            if self.is_punct(',') {
+                self.next();
+            } else if !self.is_punct('}') {
+                return self.unexpected("`,` or `}`");
+            }
+        }
+        self.punct('}')?;
+        self.semicolon();
+        Ok(Enum { name, variants })
+    }
+
+    /// An integer or a previous variant
+    fn enum_value(&mut self, variants: &[EnumVariant]) -> Result<i32> {
+        let value = match self.literal()? {
+            Literal::Int(v) => match i32::try_from(v) {
+                Ok(v) => v,
+                Err(_) => return self.error("enum value must be an i32"),
+            },
+            Literal::Ident(path) if path.len() == 1 => {
+                match variants.iter().find(|v| v.name == path[0]) {
+                    Some(variant) => variant.value,
+                    None => return self.error(format!("unknown enum variant `{}`", path[0])),
+                }
+            }
+            _ => return self.error("enum value must be an integer"),
+        };
+        Ok(value)
+    }
+
+    fn jce_const(&mut self) -> Result<Const> {
+        self.keyword("const")?;
+        let ty = self.ty()?;
+        let name = self.ident()?;
+        self.punct('=')?;
+        let value = self.literal()?;
+        self.punct(';')?;
+        Ok(Const { ty, name, value })
+    }
+
+    fn key(&mut self) -> Result<Key> {
+        self.keyword("key")?;
+        self.punct('[')?;
+        let name = self.ident()?;
+        let mut fields = Vec::new();
+        while self.is_punct(',') {
+            self.next();
+            fields.push(self.ident()?);
+        }
+        self.punct(']')?;
+        self.punct(';')?;
+        Ok(Key { name, fields })
+    }
+
+    fn interface(&mut self) -> Result<Interface> {
+        self.keyword("interface")?;
+        let name = self.ident()?;
+        self.punct('{')?;
+        let mut operations = Vec::new();
+        while !self.is_punct('}') {
+            let ret = if self.is_keyword("void") {
+                self.next();
+                None
+            } else {
+                Some(self.ty()?)
+            };
+            let name = self.ident()?;
+            self.punct('(')?;
+            let mut params = Vec::new();
+            while !self.is_punct(')') {
+                let out = self.is_keyword("out");
+                if out || self.is_keyword("routekey") {
+                    self.next();
+                }
+                let ty = self.ty()?;
+                let name = self.ident()?;
+                params.push(Param { out, ty, name });
+                if self.is_punct(',') {
+                    self.next();
+                } else if !self.is_punct(')') {
+                    return self.unexpected("`,` or `)`");
+                }
+            }
+            self.punct(')')?;
+            self.punct(';')?;
+            operations.push(Operation { name, ret, params });
+        }
+        self.punct('}')?;
+        self.semicolon();
+        Ok(Interface { name, operations })
+    }
+
+    fn ty(&mut self) -> Result<Type> {
+        let path = match self.peek() {
+            Tok::Ident(_) => self.path()?,
+            _ => return self.unexpected("type"),
+        };
+        if path.len() > 1 {
+            return Ok(Type::Custom(path));
+        }
+        let ty = match path[0].as_str() {
+            "bool" => Type::Bool,
+            "byte" => Type::Byte,
+            "short" => Type::Short,
+            "int" => Type::Int,
+            "long" => Type::Long,
+            "float" => Type::Float,
+            "double" => Type::Double,
+            "string" => Type::String,
+            "unsigned" => match self.ident()?.as_str() {
+                "byte" => Type::UnsignedByte,
+                "short" => Type::UnsignedShort,
+                "int" => Type::UnsignedInt,
+                _ => {
+                    self.index -= 1;
+                    return self.unexpected("`byte`, `short` or `int`");
+                }
+            },
+            "vector" => {
+                self.punct('<')?;
+                let item = self.ty()?;
+                self.punct('>')?;
+                Type::Vector(Box::new(item))
+            }
+            "map" => {
+                self.punct('<')?;
+                let key = self.ty()?;
+                self.punct(',')?;
+                let value = self.ty()?;
+                self.punct('>')?;
+                Type::Map(Box::new(key), Box::new(value))
+            }
+            _ => Type::Custom(path),
+        };
+        Ok(ty)
+    }
+
+    fn literal(&mut self) -> Result<Literal> {
+        let negative = self.is_punct('-');
+        if negative {
+            self.next();
+        }
+        let literal = match self.peek() {
+            Tok::Int(v) => Literal::Int(if negative { -v } else { *v }),
+            Tok::Float(v) => Literal::Float(if negative { -v } else { *v }),
+            Tok::Str(v) if !negative => Literal::String(v.clone()),
+            Tok::Ident(v) if !negative && v == "true" => Literal::Bool(true),
+            Tok::Ident(v) if !negative && v == "false" => Literal::Bool(false),
+            Tok::Ident(_) if !negative => return Ok(Literal::Ident(self.path()?)),
+            _ => return self.unexpected("literal"),
+        };
+        self.next();
+        Ok(literal)
+    }
+}
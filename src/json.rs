@@ -0,0 +1,270 @@
+use std::collections::BTreeMap as Map;
+
+use serde_json::{Number, Value as Json};
+
+use crate::de::Segment;
+use crate::{Error, ErrorKind, Result, Value};
+
+/// Expected shape of a JSON value, used by `Value::from_json` to pick the variant of `Value`
+///
+/// JSON can't tell `Int` from `Float` from `Double`, `String` from `Bytes`,
+/// or `Map` & `Object` from `List`. `Any` guesses:
+///
+/// * `null` -> `Zero`
+/// * integer -> `Int`, other number -> `Double`
+/// * string -> `String`
+/// * array -> `List`
+/// * object -> `Object` if every key is a tag, otherwise `Map` of `String` keys
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonHint {
+    Any,
+    Zero,
+    Int,
+    Float,
+    Double,
+    String,
+    Bytes,
+    /// Every element has the same hint
+    List(Box<JsonHint>),
+    /// Hint of each element, the rest are `Any`
+    Tuple(Vec<JsonHint>),
+    /// Hints of keys & values
+    Map(Box<JsonHint>, Box<JsonHint>),
+    /// Hint of each field, the rest are `Any`
+    Object(Map<u8, JsonHint>),
+}
+
+const ANY: &JsonHint = &JsonHint::Any;
+
+impl Value {
+    /// Convert to JSON
+    ///
+    /// * `Zero` -> `null`
+    /// * `Int` -> integer
+    /// * `Float` & `Double` -> number, or `"NaN"`, `"Infinity"` & `"-Infinity"`
+    /// * `String` -> string
+    /// * `Bytes` -> base64 string
+    /// * `List` -> array
+    /// * `Map` -> object if every key is a `String`, otherwise array of `[key, value]` pairs
+    /// * `Object` -> object, keys are tags, e.g. `"3"`
+    ///
+    /// `Value::from_json` with the matching `JsonHint` converts it back without loss.
+    ///
+    /// ```
+    /// use serde_jce::{JsonHint, Value};
+    ///
+    /// let val = Value::Object([(0, Value::Int(1)), (1, Value::Bytes(vec![0x12]))].into());
+    /// let json = val.to_json();
+    /// assert_eq!(json.to_string(), r#"{"0":1,"1":"Eg=="}"#);
+    ///
+    /// let hint = JsonHint::Object([(1, JsonHint::Bytes)].into());
+    /// assert_eq!(Value::from_json(&json, &hint), Ok(val));
+    /// ```
+    ///
+    pub fn to_json(&self) -> Json {
+        match self {
+            Value::Zero => Json::Null,
+            Value::Int(v) => Json::from(*v),
+            Value::Float(v) => float_to_json(*v as f64),
+            Value::Double(v) => float_to_json(*v),
+            Value::String(v) => Json::from(v.as_str()),
+            Value::Bytes(v) => Json::from(base64::encode(v)),
+            Value::List(v) => Json::Array(v.iter().map(Value::to_json).collect()),
+            Value::Map(v) => {
+                if v.keys().all(|k| matches!(k, Value::String(_))) {
+                    Json::Object(
+                        v.iter()
+                            .map(|(k, v)| match k {
+                                Value::String(k) => (k.clone(), v.to_json()),
+                                _ => unreachable!(),
+                            })
+                            .collect(),
+                    )
+                } else {
+                    Json::Array(
+                        v.iter()
+                            .map(|(k, v)| Json::Array(vec![k.to_json(), v.to_json()]))
+                            .collect(),
+                    )
+                }
+            }
+            Value::Object(v) => Json::Object(
+                v.iter()
+                    .map(|(tag, v)| (tag.to_string(), v.to_json()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Convert from JSON, ambiguous values are resolved by `hint`, see `Value::to_json` for the mapping
+    ///
+    /// Keys of a `Map` may be either the keys of an object, parsed by the hint of keys,
+    /// or `[key, value]` pairs of an array.
+    pub fn from_json(json: &Json, hint: &JsonHint) -> Result<Value> {
+        FromJson { path: Vec::new() }.value(json, hint)
+    }
+}
+
+fn float_to_json(v: f64) -> Json {
+    match Number::from_f64(v) {
+        Some(v) => Json::Number(v),
+        None if v.is_nan() => Json::from("NaN"),
+        None if v > 0.0 => Json::from("Infinity"),
+        None => Json::from("-Infinity"),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// from json
+
+struct FromJson {
+    path: Vec<Segment>,
+}
+
+impl FromJson {
+    fn nested<T>(&mut self, segment: Segment, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.path.push(segment);
+        let res = f(self).map_err(|e| e.with_path(|| self.path()));
+        self.path.pop();
+        res
+    }
+
+    fn path(&self) -> String {
+        self.path.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn value(&mut self, json: &Json, hint: &JsonHint) -> Result<Value> {
+        let val = match (hint, json) {
+            (JsonHint::Any | JsonHint::Zero, Json::Null) => Value::Zero,
+            (JsonHint::Any | JsonHint::Int, Json::Number(v)) if v.is_i64() => {
+                Value::Int(v.as_i64().unwrap())
+            }
+            (JsonHint::Any | JsonHint::Int, Json::Bool(v)) => Value::Int(*v as i64),
+            (JsonHint::Any, Json::Number(v)) => Value::Double(v.as_f64().unwrap()),
+            (JsonHint::Double, json) => match float(json) {
+                Some(v) => Value::Double(v),
+                None => return Err(mismatch("a number", json)),
+            },
+            (JsonHint::Float, json) => match float(json) {
+                Some(v) => Value::Float(v as f32),
+                None => return Err(mismatch("a number", json)),
+            },
+            (JsonHint::Any | JsonHint::String, Json::String(v)) => Value::String(v.clone()),
+            (JsonHint::Bytes, Json::String(v)) => Value::Bytes(bytes(v)?),
+            (JsonHint::Any, Json::Array(v)) => self.list(v, |_| ANY)?,
+            (JsonHint::List(hint), Json::Array(v)) => self.list(v, |_| hint)?,
+            (JsonHint::Tuple(hints), Json::Array(v)) => {
+                self.list(v, |i| hints.get(i).unwrap_or(ANY))?
+            }
+            (JsonHint::Any, Json::Object(v)) if v.keys().all(|k| k.parse::<u8>().is_ok()) => {
+                self.object(v, &Map::new())?
+            }
+            (JsonHint::Any, json) => self.map(json, &JsonHint::String, ANY)?,
+            (JsonHint::Map(key, value), json) => self.map(json, key, value)?,
+            (JsonHint::Object(hints), Json::Object(v)) => self.object(v, hints)?,
+            (hint, json) => return Err(mismatch(expecting(hint), json)),
+        };
+        Ok(val)
+    }
+
+    fn list<'a>(&mut self, v: &[Json], hint: impl Fn(usize) -> &'a JsonHint) -> Result<Value> {
+        let mut list = Vec::new();
+        for (i, v) in v.iter().enumerate() {
+            list.push(self.nested(Segment::Index(i as i64), |s| s.value(v, hint(i)))?);
+        }
+        Ok(Value::List(list))
+    }
+
+    fn map(&mut self, json: &Json, key: &JsonHint, value: &JsonHint) -> Result<Value> {
+        let mut map = Map::new();
+        match json {
+            Json::Object(v) => {
+                for (k, v) in v {
+                    self.nested(Segment::Key(k.clone()), |s| {
+                        map.insert(s.key(k, key)?, s.value(v, value)?);
+                        Ok(())
+                    })?;
+                }
+            }
+            Json::Array(v) => {
+                for (i, pair) in v.iter().enumerate() {
+                    self.nested(Segment::Index(i as i64), |s| match pair {
+                        Json::Array(pair) if pair.len() == 2 => {
+                            map.insert(s.value(&pair[0], key)?, s.value(&pair[1], value)?);
+                            Ok(())
+                        }
+                        json => Err(mismatch("a [key, value] pair", json)),
+                    })?;
+                }
+            }
+            json => return Err(mismatch("an object or an array of pairs", json)),
+        }
+        Ok(Value::Map(map))
+    }
+
+    /// Key of an object, parsed by `hint`
+    fn key(&mut self, key: &str, hint: &JsonHint) -> Result<Value> {
+        let val = match hint {
+            JsonHint::Any | JsonHint::String => Value::String(key.to_owned()),
+            JsonHint::Bytes => Value::Bytes(bytes(key)?),
+            hint => {
+                // `"12"` -> `12`, `"NaN"` stays a string
+                let json = serde_json::from_str(key).unwrap_or_else(|_| Json::from(key));
+                self.value(&json, hint)?
+            }
+        };
+        Ok(val)
+    }
+
+    fn object(
+        &mut self,
+        v: &serde_json::Map<String, Json>,
+        hints: &Map<u8, JsonHint>,
+    ) -> Result<Value> {
+        let mut obj = Map::new();
+        for (k, v) in v {
+            let tag: u8 = k
+                .parse()
+                .map_err(|_| mismatch("a tag", &Json::from(k.as_str())))?;
+            let hint = hints.get(&tag).unwrap_or(ANY);
+            obj.insert(tag, self.nested(Segment::Tag(tag), |s| s.value(v, hint))?);
+        }
+        Ok(Value::Object(obj))
+    }
+}
+
+fn float(json: &Json) -> Option<f64> {
+    match json {
+        Json::Number(v) => v.as_f64(),
+        Json::String(v) => match v.as_str() {
+            "NaN" => Some(f64::NAN),
+            "Infinity" => Some(f64::INFINITY),
+            "-Infinity" => Some(f64::NEG_INFINITY),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn bytes(v: &str) -> Result<Vec<u8>> {
+    base64::decode(v).map_err(|e| ErrorKind::Json(format!("invalid base64: {}", e)).into())
+}
+
+fn expecting(hint: &JsonHint) -> &'static str {
+    match hint {
+        JsonHint::Any => "a value",
+        JsonHint::Zero => "null",
+        JsonHint::Int => "an integer",
+        JsonHint::Float | JsonHint::Double => "a number",
+        JsonHint::String => "a string",
+        JsonHint::Bytes => "a base64 string",
+        JsonHint::List(_) | JsonHint::Tuple(_) => "an array",
+        JsonHint::Map(_, _) => "an object or an array of pairs",
+        JsonHint::Object(_) => "an object",
+    }
+}
+
+fn mismatch(expected: &str, json: &Json) -> Error {
+    ErrorKind::Json(format!("expected {}, found {}", expected, json)).into()
+}
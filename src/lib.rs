@@ -7,10 +7,31 @@
 //!
 //! * `to_bytes` - Serialize object to Jce format
 //! * `to_bytes_with_tag` - Serialize object to Jce format with specific tag
+//! * `to_writer` - Serialize object to Jce format into `std::io::Write`
+//! * `to_writer_with_tag` - Serialize object to Jce format with specific tag into `std::io::Write`
 //! * `from_bytes` - Deserialize Jce format to object
+//! * `from_reader` - Deserialize Jce format from `std::io::Read` to object
+//! * `to_value` & `from_value` - Convert between object & `Value` without bytes
 //! * `Jcebuilder` - Utils for build Jce format
 //! * `JceParser` - Utils for parse Jce format
 //! * `Value` - An recursive enum that might be able to represent all legal Jce data
+//! * `jce!` - Build a `Value` from a literal, e.g. `jce!({0: 1, 1: ["abc"]})`
+//! * `jce_typed!` - Build a `TypedValue` from a literal, e.g. `jce_typed!({0: 1i8})`
+//! * `ValueRef` - Borrowed `Value`, strings & bytes are borrowed from the input
+//! * `TypedValue` - Dynamic value that keeps the wire type of every node, re-encoded byte-for-byte
+//! * `RawValue` - Encoded bytes of a value, captured without decoding & written verbatim
+//! * `tars` - Envelope of Tars RPC, `RequestPacket`, `ResponsePacket` & `UniAttribute`
+//! * `idl` - Parse `.jce` IDL files & generate Rust code, e.g. in `build.rs`
+//! * `Change` - Differences between two `Value`s, found by `Value::diff`
+//! * `dump` - Print Jce bytes as an indented tree of offsets, tags, types & values
+//!
+//! # Cargo features
+//!
+//! * `derive` - `#[derive(Jce)]`, implement `Serialize` & `Deserialize` with `#[jce(tag = N)]` fields,
+//!   requires Rust 1.70
+//! * `tokio` - `tars::JceCodec`, a `tokio_util` codec of Tars frames
+//! * `json` - `Value::to_json` & `Value::from_json`, conversion between `Value` & `serde_json::Value`
+//! * `cli` - The `jce` command to decode, encode, validate & diff Jce bytes, run `jce help` for usage
 //!
 //! # Strongly typed data structures
 //!
@@ -43,6 +64,64 @@
 //!
 //! Please mark field with `#[serde(with = "serde_bytes")]`, which provided by `serde_bytes`.
 //!
+//! ## bare struct
+//!
+//! Tars packets & arguments are a sequence of fields without `StructBegin` & `StructEnd`.
+//!
+//! Mark the struct with `#[serde(rename = "$serde_jce::bare")]` to serialize/deserialize it this way.
+//! A bare struct is only meaningful at top level, it ends at the end of input.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(PartialEq, Debug, Serialize, Deserialize)]
+//! #[serde(rename = "$serde_jce::bare")]
+//! struct Args {
+//!     #[serde(rename = "1")]
+//!     v1: i8,
+//!     #[serde(rename = "2")]
+//!     v2: i16,
+//! }
+//!
+//! let val = Args {
+//!     v1: 0x12,
+//!     v2: 0x3456,
+//! };
+//! let bytes = vec![0x10, 0x12, 0x21, 0x34, 0x56];
+//!
+//! assert_eq!(serde_jce::to_bytes(&val), Ok(bytes.clone()));
+//! assert_eq!(serde_jce::from_bytes(&bytes), Ok(val));
+//! ```
+//!
+//! ## with enum
+//!
+//! Jce has no enum type, enum is mapped as follows:
+//!
+//! * unit variant - an int of `variant_index`, same as enum in Jce IDL
+//! * newtype variant - a struct with `variant_index` at `tag = 0` & value at `tag = 1`
+//! * tuple variant - a struct with `variant_index` at `tag = 0` & list at `tag = 1`
+//! * struct variant - a struct with `variant_index` at `tag = 0` & struct at `tag = 1`
+//!
+//! `variant_index` is the position of the variant in declaration, starting from 0.
+//! If the enum has explicit discriminants, serialize it as an integer with `serde_repr`.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(PartialEq, Debug, Serialize, Deserialize)]
+//! enum Enum {
+//!     Unit,
+//!     Newtype(i8),
+//! }
+//!
+//! assert_eq!(serde_jce::to_bytes(&Enum::Unit), Ok(vec![0x0c]));
+//! assert_eq!(serde_jce::from_bytes(&[0x0c]), Ok(Enum::Unit));
+//!
+//! let bytes = vec![0x0a, 0x00, 0x01, 0x10, 0x12, 0x0b];
+//! assert_eq!(serde_jce::to_bytes(&Enum::Newtype(0x12)), Ok(bytes.clone()));
+//! assert_eq!(serde_jce::from_bytes(&bytes), Ok(Enum::Newtype(0x12)));
+//! ```
+//!
 //! # serde_jce::Value
 //!
 //! ```
@@ -63,13 +142,46 @@
 //!
 
 mod de;
+mod diff;
+pub mod dump;
 mod error;
+pub mod idl;
+#[cfg(feature = "json")]
+mod json;
+mod macros;
+mod pointer;
+mod raw;
 mod ser;
+pub mod tars;
+mod text;
+mod typed;
 mod types;
 mod value;
+mod value_ref;
 
-pub use de::{from_bytes, Deserializer, JceParser};
-pub use error::{Error, Result};
-pub use ser::{to_bytes, to_bytes_with_tag, Jcebuilder, Serializer};
+pub use de::{
+    from_bytes, from_reader, from_value, Checkpoint, Deserializer, IoRead, JceParser, Read,
+    SliceRead,
+};
+pub use diff::{Change, ChangeKind};
+pub use error::{Error, ErrorKind, Result};
+#[cfg(feature = "json")]
+pub use json::JsonHint;
+pub use raw::RawValue;
+pub use ser::{
+    to_bytes, to_bytes_with_tag, to_value, to_writer, to_writer_with_tag, Jcebuilder, Serializer,
+};
+pub use typed::TypedValue;
 pub use types::JceType;
 pub use value::Value;
+pub use value_ref::ValueRef;
+
+#[cfg(feature = "derive")]
+pub use serde_jce_derive::Jce;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::macros::object_or_map;
+    pub use serde;
+    pub use serde_bytes;
+}
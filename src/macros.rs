@@ -0,0 +1,195 @@
+use crate::Value;
+
+/// Build a `Value` from a literal, in the shape of the text notation
+///
+/// * `zero` - `Zero`
+/// * `bytes[1, 2]` - `Bytes`
+/// * `[1, 2]` - `List`
+/// * `{0: 1, 1: "abc"}` - `Object` if every key is a tag, otherwise `Map`
+/// * `map{0: 1}` - `Map`, even if every key is a tag
+/// * any other expression - `Value::from`, e.g. `1i8`, `2.0f32`, `"abc"` or a variable
+///
+/// Floats keep their type, `2.0f32` is a `Float` & `2.0` is a `Double`.
+/// Ints are all `Int` whatever their suffix, use `jce_typed!` to control their wire types.
+///
+/// ```
+/// use serde_jce::{jce, Value};
+///
+/// let name = "abc";
+/// let val = jce!({
+///     0: 1,
+///     1: name,
+///     2: [1, -2, 3i64],
+///     3: { "k": bytes[1, 2], "f": 2.0f32 },
+///     4: map{ 0: zero },
+/// });
+/// assert_eq!(
+///     val.to_text(),
+///     r#"{0: 1, 1: "abc", 2: [1, -2, 3], 3: map{"f": 2.0f32, "k": bytes"AQI="}, 4: map{0: zero}}"#
+/// );
+/// assert!(matches!(val[3], Value::Map(_)));
+/// ```
+///
+#[macro_export]
+macro_rules! jce {
+    (zero) => {
+        $crate::Value::Zero
+    };
+    (bytes [$($byte:expr),* $(,)?]) => {
+        $crate::Value::Bytes(vec![$($byte),*])
+    };
+    ([$($tt:tt)*]) => {
+        $crate::Value::List($crate::__jce_list!(jce [] $($tt)*))
+    };
+    (map {$($tt:tt)*}) => {
+        $crate::Value::Map($crate::__jce_entries!(jce jce [] [] $($tt)*).into_iter().collect())
+    };
+    ({$($tt:tt)*}) => {
+        $crate::__private::object_or_map($crate::__jce_entries!(jce jce [] [] $($tt)*))
+    };
+    ($other:expr) => {
+        $crate::Value::from($other)
+    };
+}
+
+/// Build a `TypedValue` from a literal, in the shape of the text notation
+///
+/// * `zero` - `Zero`
+/// * `bytes[1, 2]` - `Bytes`
+/// * `[1, 2]` - `List`
+/// * `{0: 1, 1: "abc"}` - `Struct`, keys are `u8` tags & fields keep their order
+/// * `map{"k": 1}` - `Map`, entries keep their order
+/// * any other expression - `TypedValue::from`, e.g. `1i8`, `2.0f32`, `"abc"` or a variable
+///
+/// The type of an int is its wire type, `1i8` is an `I8` & `1` is an `I32` as in Rust.
+/// A string is `String1` unless it is longer than 255 bytes.
+///
+/// ```
+/// use serde_jce::jce_typed;
+///
+/// let val = jce_typed!({ 1: 12i64, 0: "abc", 2: [1i8, 2i16], 3: map{ "k": bytes[1] } });
+/// assert_eq!(
+///     val.to_text(),
+///     r#"{1: 12i64, 0: "abc", 2: [1i8, 2i16], 3: map{"k": bytes"AQ=="}}"#
+/// );
+/// assert_eq!(
+///     serde_jce::to_bytes(&jce_typed!({ 0: 1i16 })),
+///     Ok(vec![0x0a, 0x01, 0x00, 0x01, 0x0b])
+/// );
+/// ```
+///
+#[macro_export]
+macro_rules! jce_typed {
+    (zero) => {
+        $crate::TypedValue::Zero
+    };
+    (bytes [$($byte:expr),* $(,)?]) => {
+        $crate::TypedValue::Bytes(vec![$($byte),*])
+    };
+    ([$($tt:tt)*]) => {
+        $crate::TypedValue::List($crate::__jce_list!(jce_typed [] $($tt)*))
+    };
+    (map {$($tt:tt)*}) => {
+        $crate::TypedValue::Map($crate::__jce_entries!(jce_typed jce_typed [] [] $($tt)*))
+    };
+    ({$($tt:tt)*}) => {
+        $crate::TypedValue::Struct($crate::__jce_entries!(__jce_tag jce_typed [] [] $($tt)*))
+    };
+    ($other:expr) => {
+        $crate::TypedValue::from($other)
+    };
+}
+
+/// Elements of a list, one element is munched per step
+///
+/// `$m` is the macro building an element, `jce` or `jce_typed`
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __jce_list {
+    ($m:ident [$($elem:expr,)*]) => {
+        vec![$($elem,)*]
+    };
+    ($m:ident [$($elem:expr,)*] zero $(, $($rest:tt)*)?) => {
+        $crate::__jce_list!($m [$($elem,)* $crate::$m!(zero),] $($($rest)*)?)
+    };
+    ($m:ident [$($elem:expr,)*] bytes [$($bytes:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::__jce_list!($m [$($elem,)* $crate::$m!(bytes [$($bytes)*]),] $($($rest)*)?)
+    };
+    ($m:ident [$($elem:expr,)*] map {$($map:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::__jce_list!($m [$($elem,)* $crate::$m!(map {$($map)*}),] $($($rest)*)?)
+    };
+    ($m:ident [$($elem:expr,)*] [$($list:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::__jce_list!($m [$($elem,)* $crate::$m!([$($list)*]),] $($($rest)*)?)
+    };
+    ($m:ident [$($elem:expr,)*] {$($obj:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::__jce_list!($m [$($elem,)* $crate::$m!({$($obj)*}),] $($($rest)*)?)
+    };
+    ($m:ident [$($elem:expr,)*] $next:expr $(, $($rest:tt)*)?) => {
+        $crate::__jce_list!($m [$($elem,)* $crate::$m!($next),] $($($rest)*)?)
+    };
+}
+
+/// `(key, value)` pairs of a map or an object, tokens of a key are collected until a colon,
+/// then the value is munched in one step
+///
+/// `$k` & `$m` are the macros building a key & a value
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __jce_entries {
+    (@value $k:ident $m:ident [$($entry:expr,)*] [$($key:tt)+] zero $(, $($rest:tt)*)?) => {
+        $crate::__jce_entries!($k $m [$($entry,)* ($crate::$k!($($key)+), $crate::$m!(zero)),] [] $($($rest)*)?)
+    };
+    (@value $k:ident $m:ident [$($entry:expr,)*] [$($key:tt)+] bytes [$($bytes:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::__jce_entries!($k $m [$($entry,)* ($crate::$k!($($key)+), $crate::$m!(bytes [$($bytes)*])),] [] $($($rest)*)?)
+    };
+    (@value $k:ident $m:ident [$($entry:expr,)*] [$($key:tt)+] map {$($map:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::__jce_entries!($k $m [$($entry,)* ($crate::$k!($($key)+), $crate::$m!(map {$($map)*})),] [] $($($rest)*)?)
+    };
+    (@value $k:ident $m:ident [$($entry:expr,)*] [$($key:tt)+] [$($list:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::__jce_entries!($k $m [$($entry,)* ($crate::$k!($($key)+), $crate::$m!([$($list)*])),] [] $($($rest)*)?)
+    };
+    (@value $k:ident $m:ident [$($entry:expr,)*] [$($key:tt)+] {$($obj:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::__jce_entries!($k $m [$($entry,)* ($crate::$k!($($key)+), $crate::$m!({$($obj)*})),] [] $($($rest)*)?)
+    };
+    (@value $k:ident $m:ident [$($entry:expr,)*] [$($key:tt)+] $val:expr $(, $($rest:tt)*)?) => {
+        $crate::__jce_entries!($k $m [$($entry,)* ($crate::$k!($($key)+), $crate::$m!($val)),] [] $($($rest)*)?)
+    };
+    ($k:ident $m:ident [$($entry:expr,)*] []) => {
+        vec![$($entry,)*]
+    };
+    ($k:ident $m:ident [$($entry:expr,)*] [$($key:tt)+] : $($rest:tt)*) => {
+        $crate::__jce_entries!(@value $k $m [$($entry,)*] [$($key)+] $($rest)*)
+    };
+    ($k:ident $m:ident [$($entry:expr,)*] [$($key:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__jce_entries!($k $m [$($entry,)*] [$($key)* $next] $($rest)*)
+    };
+}
+
+/// A tag of a `TypedValue::Struct`
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __jce_tag {
+    ($tag:expr) => {
+        $tag
+    };
+}
+
+/// `Object` if every key is an `Int` of a tag, otherwise `Map`
+pub fn object_or_map(entries: Vec<(Value, Value)>) -> Value {
+    let tags = entries
+        .iter()
+        .all(|(k, _)| matches!(k, Value::Int(k) if u8::try_from(*k).is_ok()));
+    if tags {
+        Value::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| match k {
+                    Value::Int(k) => (k as u8, v),
+                    _ => unreachable!(),
+                })
+                .collect(),
+        )
+    } else {
+        Value::Map(entries.into_iter().collect())
+    }
+}
@@ -0,0 +1,213 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{Error, ErrorKind, Result, Value};
+
+impl Value {
+    /// Look up a value by a path of `/`-separated segments, `""` is the value itself
+    ///
+    /// Each segment is looked up according to the value it applies to:
+    ///
+    /// * `Object` - a tag, e.g. `/3`
+    /// * `List` - an index, e.g. `/0`
+    /// * `Map` - a key in text notation, e.g. `/1` or `/"key"`, `/` may be used inside quotes
+    ///
+    /// A malformed path is `ErrorKind::InvalidPath`, a missing value is `ErrorKind::PathNotFound`,
+    /// `Error::path` is the prefix of the path that failed.
+    ///
+    /// ```
+    /// use serde_jce::{ErrorKind, Value};
+    ///
+    /// let val = Value::from_text(r#"{3: [map{"key": [1, 2]}]}"#).unwrap();
+    /// assert_eq!(val.pointer(r#"/3/0/"key"/1"#), Ok(&Value::Int(2)));
+    ///
+    /// let err = val.pointer(r#"/3/1/"key""#).unwrap_err();
+    /// assert_eq!(err.kind(), &ErrorKind::PathNotFound);
+    /// assert_eq!(err.path(), "/3/1");
+    /// ```
+    ///
+    pub fn pointer(&self, path: &str) -> Result<&Value> {
+        let mut val = self;
+        for (seg, end) in split(path)? {
+            val = match val {
+                Value::Object(obj) => seg.parse().ok().and_then(|tag: u8| obj.get(&tag)),
+                Value::List(list) => seg.parse().ok().and_then(|i: usize| list.get(i)),
+                Value::Map(map) => map.get(&key(seg).map_err(|e| at(e, path, end))?),
+                _ => None,
+            }
+            .ok_or_else(|| at(ErrorKind::PathNotFound.into(), path, end))?;
+        }
+        Ok(val)
+    }
+
+    /// Mutable `Value::pointer`
+    pub fn pointer_mut(&mut self, path: &str) -> Result<&mut Value> {
+        let segs = split(path)?;
+        walk_mut(self, path, &segs)
+    }
+
+    /// Set the value at `path`, return the replaced value
+    ///
+    /// Every segment but the last must exist, see `Value::pointer`.
+    /// The last one may add a field to an `Object`, an entry to a `Map`,
+    /// or an element to the end of a `List`.
+    ///
+    /// ```
+    /// use serde_jce::Value;
+    ///
+    /// let mut val = Value::from_text(r#"{3: [1], 4: map{}}"#).unwrap();
+    /// assert_eq!(val.set_at_path("/3/0", Value::Int(2)), Ok(Some(Value::Int(1))));
+    /// assert_eq!(val.set_at_path("/3/1", Value::Int(3)), Ok(None));
+    /// assert_eq!(val.set_at_path(r#"/4/"key""#, Value::Zero), Ok(None));
+    /// assert_eq!(val.set_at_path("/5", Value::from("a")), Ok(None));
+    /// assert_eq!(
+    ///     val.to_text(),
+    ///     r#"{3: [2, 3], 4: map{"key": zero}, 5: "a"}"#
+    /// );
+    /// ```
+    ///
+    pub fn set_at_path(&mut self, path: &str, value: Value) -> Result<Option<Value>> {
+        let segs = split(path)?;
+        let (&(seg, end), parent) = match segs.split_last() {
+            Some(last) => last,
+            None => return Ok(Some(std::mem::replace(self, value))),
+        };
+        let err = |kind: ErrorKind| at(kind.into(), path, end);
+        match walk_mut(self, path, parent)? {
+            Value::Object(obj) => {
+                let tag = seg
+                    .parse()
+                    .map_err(|_| err(ErrorKind::InvalidPath(format!("invalid tag `{}`", seg))))?;
+                Ok(obj.insert(tag, value))
+            }
+            Value::List(list) => match seg.parse::<usize>() {
+                Ok(i) if i < list.len() => Ok(Some(std::mem::replace(&mut list[i], value))),
+                Ok(i) if i == list.len() => {
+                    list.push(value);
+                    Ok(None)
+                }
+                _ => Err(err(ErrorKind::PathNotFound)),
+            },
+            Value::Map(map) => Ok(map.insert(key(seg).map_err(|e| at(e, path, end))?, value)),
+            _ => Err(err(ErrorKind::PathNotFound)),
+        }
+    }
+}
+
+fn walk_mut<'a>(
+    mut val: &'a mut Value,
+    path: &str,
+    segs: &[(&str, usize)],
+) -> Result<&'a mut Value> {
+    for &(seg, end) in segs {
+        val = match val {
+            Value::Object(obj) => seg.parse().ok().and_then(move |tag: u8| obj.get_mut(&tag)),
+            Value::List(list) => seg.parse().ok().and_then(move |i: usize| list.get_mut(i)),
+            Value::Map(map) => map.get_mut(&key(seg).map_err(|e| at(e, path, end))?),
+            _ => None,
+        }
+        .ok_or_else(|| at(ErrorKind::PathNotFound.into(), path, end))?;
+    }
+    Ok(val)
+}
+
+/// Segments of `path` & the end of each segment in `path`
+fn split(path: &str) -> Result<Vec<(&str, usize)>> {
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !path.starts_with('/') {
+        return Err(ErrorKind::InvalidPath(format!("`{}` doesn't start with `/`", path)).into());
+    }
+    let mut segs = Vec::new();
+    let mut start = 1;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in path.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '/' if !quoted => {
+                segs.push((&path[start..i], i));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quoted {
+        return Err(ErrorKind::InvalidPath(format!("unterminated string in `{}`", path)).into());
+    }
+    segs.push((&path[start..], path.len()));
+    Ok(segs)
+}
+
+/// Key of a map in text notation
+fn key(seg: &str) -> Result<Value> {
+    Value::from_text(seg)
+        .map_err(|_| ErrorKind::InvalidPath(format!("invalid key `{}`", seg)).into())
+}
+
+fn at(err: Error, path: &str, end: usize) -> Error {
+    err.with_path(|| path[..end].to_owned())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl Index
+
+fn index_error(index: impl std::fmt::Debug) -> ! {
+    panic!("no value at index {:?}", index)
+}
+
+/// Tag of an `Object`, index of a `List` or int key of a `Map`, panics if there is no such value
+///
+/// Use `Value::pointer` to get an error instead.
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        match self {
+            Value::Object(obj) => u8::try_from(index).ok().and_then(|tag| obj.get(&tag)),
+            Value::List(list) => list.get(index),
+            Value::Map(map) => map.get(&Value::Int(index as i64)),
+            _ => None,
+        }
+        .unwrap_or_else(|| index_error(index))
+    }
+}
+
+impl IndexMut<usize> for Value {
+    fn index_mut(&mut self, index: usize) -> &mut Value {
+        match self {
+            Value::Object(obj) => u8::try_from(index)
+                .ok()
+                .and_then(move |tag| obj.get_mut(&tag)),
+            Value::List(list) => list.get_mut(index),
+            Value::Map(map) => map.get_mut(&Value::Int(index as i64)),
+            _ => None,
+        }
+        .unwrap_or_else(|| index_error(index))
+    }
+}
+
+/// String key of a `Map`, panics if there is no such value
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        match self {
+            Value::Map(map) => map.get(&Value::from(key)),
+            _ => None,
+        }
+        .unwrap_or_else(|| index_error(key))
+    }
+}
+
+impl IndexMut<&str> for Value {
+    fn index_mut(&mut self, key: &str) -> &mut Value {
+        match self {
+            Value::Map(map) => map.get_mut(&Value::from(key)),
+            _ => None,
+        }
+        .unwrap_or_else(|| index_error(key))
+    }
+}
@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::RAW_VALUE;
+use crate::{ErrorKind, JceParser, JceType, Result};
+
+/// Encoded bytes of a Jce value, head included, which are not decoded
+///
+/// * Deserializing captures the bytes of a value, borrowed from the input if possible
+/// * Serializing writes the bytes verbatim, the tag of the head is replaced by the tag of the field
+/// * `serde_jce::from_bytes(raw.get())` decodes it later
+///
+/// Only meaningful with `serde_jce`'s `Deserializer` & `Serializer`.
+///
+/// # Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use serde_jce::RawValue;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Packet<'a> {
+///     #[serde(rename = "0")]
+///     id: i32,
+///     #[serde(rename = "1", borrow)]
+///     body: RawValue<'a>,
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Forward<'a> {
+///     #[serde(rename = "0", borrow)]
+///     body: RawValue<'a>,
+/// }
+///
+/// let bytes = [0x0a, 0x00, 0x01, 0x19, 0x00, 0x01, 0x00, 0x12, 0x0b];
+/// let packet: Packet = serde_jce::from_bytes(&bytes).unwrap();
+/// assert_eq!(packet.body.get(), [0x19, 0x00, 0x01, 0x00, 0x12]);
+/// assert_eq!(serde_jce::from_bytes::<Vec<i8>>(packet.body.get()), Ok(vec![0x12]));
+///
+/// let forward = Forward { body: packet.body };
+/// assert_eq!(
+///     serde_jce::to_bytes(&forward),
+///     Ok(vec![0x0a, 0x09, 0x00, 0x01, 0x00, 0x12, 0x0b])
+/// );
+/// ```
+///
+/// # Reader
+///
+/// Bytes can't be borrowed from a `std::io::Read`, so `RawValue` can't be deserialized by `from_reader`.
+/// Use `Deserializer::from_reader` instead & `into_owned` the captured values.
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RawValue<'a> {
+    bytes: Cow<'a, [u8]>,
+}
+
+impl<'a> RawValue<'a> {
+    /// Check that `bytes` is exactly one value
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        Self::check(bytes)?;
+        Ok(Self {
+            bytes: Cow::Borrowed(bytes),
+        })
+    }
+
+    /// Owned version of `new`
+    pub fn from_vec(bytes: Vec<u8>) -> Result<RawValue<'static>> {
+        Self::check(&bytes)?;
+        Ok(RawValue {
+            bytes: Cow::Owned(bytes),
+        })
+    }
+
+    fn check(bytes: &[u8]) -> Result<()> {
+        let mut parser = JceParser::from_bytes(bytes);
+        parser.raw()?;
+        if parser.done() {
+            Ok(())
+        } else {
+            Err(ErrorKind::TrailingBytes.into())
+        }
+    }
+
+    /// Encoded bytes, head included
+    pub fn get(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn tag(&self) -> u8 {
+        match self.bytes[0] >> 4 {
+            0x0f => self.bytes[1],
+            tag => tag,
+        }
+    }
+
+    pub fn jce_type(&self) -> JceType {
+        // checked on creation & deserialization
+        JceType::try_from(self.bytes[0] & 0x0f).unwrap()
+    }
+
+    pub fn into_owned(self) -> RawValue<'static> {
+        RawValue {
+            bytes: Cow::Owned(self.bytes.into_owned()),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for RawValue<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_tuple("RawValue")
+            .field(&self.jce_type())
+            .field(&self.bytes)
+            .finish()
+    }
+}
+
+impl<'a> Serialize for RawValue<'a> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(RAW_VALUE, serde_bytes::Bytes::new(&self.bytes))
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RawValue<'a> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct RawVisitor;
+
+        impl<'de> Visitor<'de> for RawVisitor {
+            type Value = RawValue<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a raw Jce value")
+            }
+
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> std::result::Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                RawValue::check(v).map_err(E::custom)?;
+                Ok(RawValue {
+                    bytes: Cow::Borrowed(v),
+                })
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> std::result::Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                RawValue::check(v).map_err(E::custom)?;
+                Ok(RawValue {
+                    bytes: Cow::Owned(v.to_vec()),
+                })
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(RAW_VALUE, RawVisitor)
+    }
+}
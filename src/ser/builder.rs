@@ -1,4 +1,6 @@
-use crate::types::JceType;
+use std::io;
+
+use super::writer::JceWriter;
 
 /// Manually construct Jce format.
 ///
@@ -19,88 +21,61 @@ use crate::types::JceType;
 /// * `Jcebuilder::BYTES_MAX_LENGTH` - The maximum length of the bytes, the rest will be trimmed
 ///
 pub struct Jcebuilder {
-    bytes: Vec<u8>,
+    writer: JceWriter<Vec<u8>>,
 }
 
-impl Jcebuilder {
-    pub fn new() -> Self {
-        Self { bytes: Vec::new() }
-    }
-
-    pub fn done(self) -> Vec<u8> {
-        self.bytes
+impl Default for Jcebuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Jcebuilder {
-    fn push_head(&mut self, tag: u8, tp: JceType) -> &mut Self {
-        if tag < 15 {
-            self.push_byte((tag << 4) + (tp as u8));
-        } else {
-            self.push_byte(0xf0 + (tp as u8));
-            self.push_byte(tag);
+    pub fn new() -> Self {
+        Self {
+            writer: JceWriter::new(Vec::new()),
         }
-        self
     }
 
-    fn push_byte(&mut self, byte: u8) -> &mut Self {
-        self.bytes.push(byte);
-        self
+    pub fn done(self) -> Vec<u8> {
+        self.writer.into_inner()
     }
+}
 
-    fn push_bytes<T>(&mut self, bytes: T) -> &mut Self
-    where
-        T: AsRef<[u8]>,
-    {
-        self.bytes.extend_from_slice(bytes.as_ref());
-        self
-    }
+/// Writing into a `Vec<u8>` never fails
+fn infallible(res: io::Result<()>) {
+    res.expect("writing to Vec<u8> never fails")
 }
 
 impl Jcebuilder {
     pub fn i8(&mut self, tag: u8, v: i8) -> &mut Self {
-        if v == 0 {
-            self.zero(tag)
-        } else {
-            self.push_head(tag, JceType::I8).push_bytes(v.to_be_bytes())
-        }
+        infallible(self.writer.i8(tag, v));
+        self
     }
 
     pub fn i16(&mut self, tag: u8, v: i16) -> &mut Self {
-        if std::i8::MIN as i16 <= v && v <= std::i8::MAX as i16 {
-            self.i8(tag, v as i8)
-        } else {
-            self.push_head(tag, JceType::I16)
-                .push_bytes(v.to_be_bytes())
-        }
+        infallible(self.writer.i16(tag, v));
+        self
     }
 
     pub fn i32(&mut self, tag: u8, v: i32) -> &mut Self {
-        if std::i16::MIN as i32 <= v && v <= std::i16::MAX as i32 {
-            self.i16(tag, v as i16)
-        } else {
-            self.push_head(tag, JceType::I32)
-                .push_bytes(v.to_be_bytes())
-        }
+        infallible(self.writer.i32(tag, v));
+        self
     }
 
     pub fn i64(&mut self, tag: u8, v: i64) -> &mut Self {
-        if std::i32::MIN as i64 <= v && v <= std::i32::MAX as i64 {
-            self.i32(tag, v as i32)
-        } else {
-            self.push_head(tag, JceType::I64)
-                .push_bytes(v.to_be_bytes())
-        }
+        infallible(self.writer.i64(tag, v));
+        self
     }
 
     pub fn f32(&mut self, tag: u8, v: f32) -> &mut Self {
-        self.push_head(tag, JceType::F32)
-            .push_bytes(v.to_be_bytes())
+        infallible(self.writer.f32(tag, v));
+        self
     }
 
     pub fn f64(&mut self, tag: u8, v: f64) -> &mut Self {
-        self.push_head(tag, JceType::F64)
-            .push_bytes(v.to_be_bytes())
+        infallible(self.writer.f64(tag, v));
+        self
     }
 
     pub const STRING_MAX_LENGTH: usize = u32::MAX as usize;
@@ -116,17 +91,8 @@ impl Jcebuilder {
     where
         T: AsRef<str>,
     {
-        let v = v.as_ref().as_bytes();
-        if v.len() <= 255 {
-            self.push_head(tag, JceType::String1)
-                .push_byte(v.len() as u8)
-                .push_bytes(v)
-        } else {
-            let n = std::cmp::min(v.len(), Self::STRING_MAX_LENGTH);
-            self.push_head(tag, JceType::String4)
-                .push_bytes((n as u32).to_be_bytes())
-                .push_bytes(&v[..n])
-        }
+        infallible(self.writer.str(tag, v.as_ref(), Self::STRING_MAX_LENGTH));
+        self
     }
 
     /// Insert a map header
@@ -136,7 +102,8 @@ impl Jcebuilder {
     /// * `map value` - `tag = 1`
     ///
     pub fn map_begin(&mut self, tag: u8, len: i32) -> &mut Self {
-        self.push_head(tag, JceType::Map).i32(0, len)
+        infallible(self.writer.map_begin(tag, len));
+        self
     }
 
     /// Insert a map header
@@ -145,19 +112,23 @@ impl Jcebuilder {
     /// * `list key` - `tag = 0`
     ///
     pub fn list_begin(&mut self, tag: u8, len: i32) -> &mut Self {
-        self.push_head(tag, JceType::List).i32(0, len)
+        infallible(self.writer.list_begin(tag, len));
+        self
     }
 
     pub fn struct_begin(&mut self, tag: u8) -> &mut Self {
-        self.push_head(tag, JceType::StructBegin)
+        infallible(self.writer.struct_begin(tag));
+        self
     }
 
     pub fn struct_end(&mut self) -> &mut Self {
-        self.push_head(0, JceType::StructEnd)
+        infallible(self.writer.struct_end());
+        self
     }
 
     pub fn zero(&mut self, tag: u8) -> &mut Self {
-        self.push_head(tag, JceType::Zero)
+        infallible(self.writer.zero(tag));
+        self
     }
 
     pub const BYTES_MAX_LENGTH: usize = i32::MAX as usize;
@@ -173,11 +144,7 @@ impl Jcebuilder {
     where
         T: AsRef<[u8]>,
     {
-        let v = v.as_ref();
-        let n = std::cmp::min(v.len(), Self::BYTES_MAX_LENGTH);
-        self.push_head(tag, JceType::Bytes)
-            .push_head(0, JceType::I8)
-            .i32(0, n as i32)
-            .push_bytes(&v[..n])
+        infallible(self.writer.bytes(tag, v.as_ref(), Self::BYTES_MAX_LENGTH));
+        self
     }
 }
@@ -1,5 +1,9 @@
 mod builder;
 mod serializer;
+mod value;
+mod writer;
 
 pub use builder::Jcebuilder;
-pub use serializer::{to_bytes, to_bytes_with_tag, Serializer};
+pub use serializer::{to_bytes, to_bytes_with_tag, to_writer, to_writer_with_tag, Serializer};
+pub use value::to_value;
+pub(crate) use writer::JceWriter;
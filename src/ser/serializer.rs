@@ -1,11 +1,19 @@
-use serde::{ser, serde_if_integer128, Serialize};
+use std::io;
 
-use crate::{Error, Jcebuilder, Result};
+use serde::{ser, Serialize};
+
+use super::writer::JceWriter;
+use crate::types::{BARE_STRUCT, RAW_VALUE};
+use crate::{Error, ErrorKind, JceType, Jcebuilder, Result};
 
 /// A structure for serializing Rust values into Jce.
-pub struct Serializer {
+///
+/// Output goes to a `Vec<u8>` by default, `Serializer::from_writer` streams into any `std::io::Write`.
+pub struct Serializer<W = Vec<u8>> {
     pub tag: u8,
-    builder: Jcebuilder,
+    writer: JceWriter<W>,
+    /// The next bytes are the encoded bytes of a `RawValue`
+    raw: bool,
 }
 
 /// Serialize the given data to Jce format.
@@ -75,20 +83,100 @@ where
     Ok(serializer.done())
 }
 
+/// Serialize the given data as Jce format into a `std::io::Write`.
+///
+/// Every primitive is written with a separate small `write_all` call,
+/// wrap unbuffered sinks such as `TcpStream` or `File` in a `std::io::BufWriter`.
+///
+/// # Example
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Struct {
+///     #[serde(rename = "0")]
+///     v0: i8,
+///     #[serde(rename = "1")]
+///     v1: i16,
+/// }
+/// let val = Struct {
+///     v0: 0x12,
+///     v1: 0x3456,
+/// };
+/// let mut buf = Vec::new();
+/// serde_jce::to_writer(&mut buf, &val).unwrap();
+/// serde_jce::to_writer(&mut buf, &val).unwrap();
+/// assert_eq!(
+///     buf,
+///     [0x0a, 0x00, 0x12, 0x11, 0x34, 0x56, 0x0b, 0x0a, 0x00, 0x12, 0x11, 0x34, 0x56, 0x0b]
+/// );
+/// ```
+///
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<()>
+where
+    W: io::Write,
+    T: Serialize,
+{
+    let mut serializer = Serializer::from_writer(writer);
+    value.serialize(&mut serializer)
+}
+
+/// Serialize the given data as Jce format with specific tag into a `std::io::Write`.
+pub fn to_writer_with_tag<W, T>(writer: W, tag: u8, value: &T) -> Result<()>
+where
+    W: io::Write,
+    T: Serialize,
+{
+    let mut serializer = Serializer::from_writer(writer);
+    serializer.tag = tag;
+    value.serialize(&mut serializer)
+}
+
+impl Default for Serializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Serializer {
     pub fn new() -> Self {
+        Self::from_writer(Vec::new())
+    }
+
+    pub fn done(self) -> Vec<u8> {
+        self.writer.into_inner()
+    }
+}
+
+impl<W> Serializer<W>
+where
+    W: io::Write,
+{
+    pub fn from_writer(writer: W) -> Self {
         Self {
             tag: 0,
-            builder: Jcebuilder::new(),
+            writer: JceWriter::new(writer),
+            raw: false,
         }
     }
 
-    pub fn done(self) -> Vec<u8> {
-        self.builder.done()
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    /// Begin the wrapper struct of a data-carrying enum variant
+    fn variant_begin(&mut self, variant_index: u32) -> Result<()> {
+        self.writer.struct_begin(self.tag)?;
+        self.writer.i64(0, variant_index as i64)?;
+        Ok(())
     }
 }
 
-impl<'a> ser::Serializer for &'a mut Serializer {
+impl<'a, W> ser::Serializer for &'a mut Serializer<W>
+where
+    W: io::Write,
+{
     type Ok = ();
     type Error = Error;
 
@@ -97,40 +185,38 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
     type SerializeMap = Self;
-    type SerializeStruct = StructSerializer<'a>;
-    type SerializeStructVariant = StructSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a, W>;
+    type SerializeStructVariant = StructSerializer<'a, W>;
 
     fn serialize_bool(self, v: bool) -> Result<()> {
         self.serialize_i8(v as i8)
     }
 
     fn serialize_i8(self, v: i8) -> Result<()> {
-        self.builder.i8(self.tag, v);
+        self.writer.i8(self.tag, v)?;
         Ok(())
     }
 
     fn serialize_i16(self, v: i16) -> Result<()> {
-        self.builder.i16(self.tag, v);
+        self.writer.i16(self.tag, v)?;
         Ok(())
     }
 
     fn serialize_i32(self, v: i32) -> Result<()> {
-        self.builder.i32(self.tag, v);
+        self.writer.i32(self.tag, v)?;
         Ok(())
     }
 
     fn serialize_i64(self, v: i64) -> Result<()> {
-        self.builder.i64(self.tag, v);
+        self.writer.i64(self.tag, v)?;
         Ok(())
     }
 
-    serde_if_integer128! {
-        fn serialize_i128(self, v: i128) -> Result<()> {
-            if v <= std::i64::MAX as i128 {
-                self.serialize_i64(v as i64)
-            } else {
-                Err(Error::IntTooBig)
-            }
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        if v <= i64::MAX as i128 {
+            self.serialize_i64(v as i64)
+        } else {
+            Err(ErrorKind::IntTooBig.into())
         }
     }
 
@@ -147,30 +233,28 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_u64(self, v: u64) -> Result<()> {
-        if v <= std::i64::MAX as u64 {
+        if v <= i64::MAX as u64 {
             self.serialize_i64(v as i64)
         } else {
-            Err(Error::IntTooBig)
+            Err(ErrorKind::IntTooBig.into())
         }
     }
 
-    serde_if_integer128! {
-        fn serialize_u128(self, v: u128) -> Result<()> {
-            if v <= std::i64::MAX as u128 {
-                self.serialize_i64(v as i64)
-            } else {
-                Err(Error::IntTooBig)
-            }
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        if v <= i64::MAX as u128 {
+            self.serialize_i64(v as i64)
+        } else {
+            Err(ErrorKind::IntTooBig.into())
         }
     }
 
     fn serialize_f32(self, v: f32) -> Result<()> {
-        self.builder.f32(self.tag, v);
+        self.writer.f32(self.tag, v)?;
         Ok(())
     }
 
     fn serialize_f64(self, v: f64) -> Result<()> {
-        self.builder.f64(self.tag, v);
+        self.writer.f64(self.tag, v)?;
         Ok(())
     }
 
@@ -180,24 +264,35 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 
     fn serialize_str(self, v: &str) -> Result<()> {
         if v.len() <= Jcebuilder::STRING_MAX_LENGTH {
-            self.builder.str(self.tag, v);
+            self.writer
+                .str(self.tag, v, Jcebuilder::STRING_MAX_LENGTH)?;
             Ok(())
         } else {
-            Err(Error::StringTooLong)
+            Err(ErrorKind::StringTooLong.into())
         }
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<()> {
-        if v.len() <= Jcebuilder::BYTES_MAX_LENGTH {
-            self.builder.bytes(self.tag, v);
+        if self.raw {
+            self.raw = false;
+            let head = match v.first() {
+                Some(&head) if head >> 4 != 0x0f || v.len() >= 2 => head,
+                _ => return Err(ErrorKind::NotEnoughtBytes.into()),
+            };
+            let tp = JceType::try_from(head & 0x0f)?;
+            self.writer.raw(self.tag, tp, v)?;
+            Ok(())
+        } else if v.len() <= Jcebuilder::BYTES_MAX_LENGTH {
+            self.writer
+                .bytes(self.tag, v, Jcebuilder::BYTES_MAX_LENGTH)?;
             Ok(())
         } else {
-            Err(Error::BytesTooLong)
+            Err(ErrorKind::BytesTooLong.into())
         }
     }
 
     fn serialize_none(self) -> Result<()> {
-        self.builder.zero(self.tag);
+        self.writer.zero(self.tag)?;
         Ok(())
     }
 
@@ -216,45 +311,63 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         self.serialize_unit()
     }
 
+    /// Unit variant is an int of `variant_index`
     fn serialize_unit_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
-        variant: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
     ) -> Result<()> {
-        variant.serialize(self)
+        self.serialize_u32(variant_index)
     }
 
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    /// `RawValue` writes its bytes with the head re-tagged
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(self)
+        if name == RAW_VALUE {
+            self.raw = true;
+            let res = value.serialize(&mut *self);
+            self.raw = false;
+            res
+        } else {
+            value.serialize(self)
+        }
     }
 
+    /// Newtype variant is a struct with `variant_index` at `tag = 0` & value at `tag = 1`
     fn serialize_newtype_variant<T>(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         _variant: &'static str,
         value: &T,
     ) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(self)
+        self.variant_begin(variant_index)?;
+
+        let cur_tag = self.tag;
+        self.tag = 1;
+        value.serialize(&mut *self)?;
+        self.tag = cur_tag;
+
+        self.writer.struct_end()?;
+        Ok(())
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
         match len {
             Some(len) => match len.try_into() {
                 Ok(len) => {
-                    self.builder.list_begin(self.tag, len);
+                    self.writer.list_begin(self.tag, len)?;
                     Ok(self)
                 }
-                Err(_) => Err(Error::SeqTooLong),
+                Err(_) => Err(ErrorKind::SeqTooLong.into()),
             },
-            None => Err(Error::NeedLength),
+            None => Err(ErrorKind::NeedLength.into()),
         }
     }
 
@@ -270,48 +383,66 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         self.serialize_tuple(len)
     }
 
+    /// Tuple variant is a struct with `variant_index` at `tag = 0` & list at `tag = 1`
     fn serialize_tuple_variant(
         self,
-        name: &'static str,
-        _variant_index: u32,
+        _name: &'static str,
+        variant_index: u32,
         _variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
-        self.serialize_tuple_struct(name, len)
+        match len.try_into() {
+            Ok(len) => {
+                self.variant_begin(variant_index)?;
+                self.writer.list_begin(1, len)?;
+                Ok(self)
+            }
+            Err(_) => Err(ErrorKind::SeqTooLong.into()),
+        }
     }
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
         match len {
             Some(len) => match len.try_into() {
                 Ok(len) => {
-                    self.builder.map_begin(self.tag, len);
+                    self.writer.map_begin(self.tag, len)?;
                     Ok(self)
                 }
-                Err(_) => Err(Error::MapTooLong),
+                Err(_) => Err(ErrorKind::MapTooLong.into()),
             },
-            None => Err(Error::NeedLength),
+            None => Err(ErrorKind::NeedLength.into()),
         }
     }
 
-    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
-        self.builder.struct_begin(self.tag);
-        Ok(Self::SerializeStruct::new(self))
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        if name == BARE_STRUCT {
+            Ok(Self::SerializeStruct::new_bare(self))
+        } else {
+            self.writer.struct_begin(self.tag)?;
+            Ok(Self::SerializeStruct::new(self))
+        }
     }
 
+    /// Struct variant is a struct with `variant_index` at `tag = 0` & struct at `tag = 1`
     fn serialize_struct_variant(
         self,
-        name: &'static str,
-        _variant_index: u32,
+        _name: &'static str,
+        variant_index: u32,
         _variant: &'static str,
-        len: usize,
+        _len: usize,
     ) -> Result<Self::SerializeStructVariant> {
-        self.serialize_struct(name, len)
+        self.variant_begin(variant_index)?;
+        self.writer.struct_begin(1)?;
+        Ok(Self::SerializeStructVariant::new(self))
     }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
-impl<'a> ser::SerializeSeq for &'a mut Serializer {
+impl<W> ser::SerializeSeq for &mut Serializer<W>
+where
+    W: io::Write,
+{
     type Ok = ();
     type Error = Error;
 
@@ -333,7 +464,10 @@ impl<'a> ser::SerializeSeq for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeTuple for &'a mut Serializer {
+impl<W> ser::SerializeTuple for &mut Serializer<W>
+where
+    W: io::Write,
+{
     type Ok = ();
     type Error = Error;
 
@@ -349,7 +483,10 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
+impl<W> ser::SerializeTupleStruct for &mut Serializer<W>
+where
+    W: io::Write,
+{
     type Ok = ();
     type Error = Error;
 
@@ -365,7 +502,10 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
+impl<W> ser::SerializeTupleVariant for &mut Serializer<W>
+where
+    W: io::Write,
+{
     type Ok = ();
     type Error = Error;
 
@@ -377,11 +517,15 @@ impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
     }
 
     fn end(self) -> Result<()> {
-        ser::SerializeSeq::end(self)
+        self.writer.struct_end()?;
+        Ok(())
     }
 }
 
-impl<'a> ser::SerializeMap for &'a mut Serializer {
+impl<W> ser::SerializeMap for &mut Serializer<W>
+where
+    W: io::Write,
+{
     type Ok = ();
     type Error = Error;
 
@@ -418,21 +562,35 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-pub struct StructSerializer<'a> {
-    ser: &'a mut Serializer,
+pub struct StructSerializer<'a, W> {
+    ser: &'a mut Serializer<W>,
     tags: std::collections::HashSet<u8>,
+    bare: bool,
 }
 
-impl<'a> StructSerializer<'a> {
-    pub fn new(ser: &'a mut Serializer) -> Self {
+impl<'a, W> StructSerializer<'a, W> {
+    pub fn new(ser: &'a mut Serializer<W>) -> Self {
+        Self {
+            ser,
+            tags: std::collections::HashSet::new(),
+            bare: false,
+        }
+    }
+
+    /// Fields without `StructBegin` & `StructEnd`
+    pub fn new_bare(ser: &'a mut Serializer<W>) -> Self {
         Self {
-            ser: ser,
+            ser,
             tags: std::collections::HashSet::new(),
+            bare: true,
         }
     }
 }
 
-impl<'a> ser::SerializeStruct for StructSerializer<'a> {
+impl<'a, W> ser::SerializeStruct for StructSerializer<'a, W>
+where
+    W: io::Write,
+{
     type Ok = ();
     type Error = Error;
 
@@ -449,20 +607,25 @@ impl<'a> ser::SerializeStruct for StructSerializer<'a> {
                     self.ser.tag = cur_tag;
                     Ok(())
                 } else {
-                    Err(Error::DuplicateFieldTag)
+                    Err(ErrorKind::DuplicateFieldTag.into())
                 }
             }
-            Err(_) => Err(Error::ErrorFieldTag),
+            Err(_) => Err(ErrorKind::ErrorFieldTag.into()),
         }
     }
 
     fn end(self) -> Result<()> {
-        self.ser.builder.struct_end();
+        if !self.bare {
+            self.ser.writer.struct_end()?;
+        }
         Ok(())
     }
 }
 
-impl<'a> ser::SerializeStructVariant for StructSerializer<'a> {
+impl<'a, W> ser::SerializeStructVariant for StructSerializer<'a, W>
+where
+    W: io::Write,
+{
     type Ok = ();
     type Error = Error;
 
@@ -474,6 +637,8 @@ impl<'a> ser::SerializeStructVariant for StructSerializer<'a> {
     }
 
     fn end(self) -> Result<()> {
-        ser::SerializeStruct::end(self)
+        self.ser.writer.struct_end()?;
+        self.ser.writer.struct_end()?;
+        Ok(())
     }
 }
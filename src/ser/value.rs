@@ -0,0 +1,404 @@
+use std::collections::BTreeMap as Map;
+
+use serde::{ser, Serialize};
+
+use crate::types::RAW_VALUE;
+use crate::{Error, ErrorKind, Result, Value};
+
+/// Serialize the given data to a `Value`, without encoding it to bytes
+///
+/// The `Value` is what `from_bytes` decodes from the bytes of `to_bytes`,
+/// struct fields are the tags of an `Object`, maps are `Map`s,
+/// but `0` stays `Int(0)` instead of becoming `Zero`, so `Some(0)` is not `None`.
+///
+/// # Example
+///
+/// ```
+/// use serde::Serialize;
+/// use serde_jce::Value;
+///
+/// #[derive(Serialize)]
+/// struct Struct {
+///     #[serde(rename = "0")]
+///     v0: Option<i8>,
+///     #[serde(rename = "1")]
+///     v1: Option<i8>,
+/// }
+/// let val = Struct {
+///     v0: Some(0),
+///     v1: None,
+/// };
+/// assert_eq!(
+///     serde_jce::to_value(&val).unwrap(),
+///     Value::Object([(0, Value::Int(0)), (1, Value::Zero)].into())
+/// );
+/// ```
+///
+pub fn to_value<T>(value: &T) -> Result<Value>
+where
+    T: Serialize,
+{
+    value.serialize(Serializer)
+}
+
+/// Data-carrying enum variant is an `Object` with `variant_index` at `tag = 0` & data at `tag = 1`
+fn variant(variant_index: u32, data: Value) -> Value {
+    Value::Object([(0, Value::Int(variant_index as i64)), (1, data)].into())
+}
+
+fn int<T>(v: T) -> Result<Value>
+where
+    i64: TryFrom<T>,
+{
+    match i64::try_from(v) {
+        Ok(v) => Ok(Value::Int(v)),
+        Err(_) => Err(ErrorKind::IntTooBig.into()),
+    }
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = ListSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = ObjectSerializer;
+    type SerializeStructVariant = ObjectSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Value> {
+        Ok(Value::Int(v as i64))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value> {
+        Ok(Value::Int(v as i64))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value> {
+        Ok(Value::Int(v as i64))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value> {
+        Ok(Value::Int(v as i64))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value> {
+        Ok(Value::Int(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value> {
+        int(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value> {
+        Ok(Value::Int(v as i64))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value> {
+        Ok(Value::Int(v as i64))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value> {
+        Ok(Value::Int(v as i64))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value> {
+        int(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value> {
+        int(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value> {
+        Ok(Value::Float(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value> {
+        Ok(Value::Double(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value> {
+        Ok(Value::Bytes(v.to_owned()))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Zero)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Zero)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        self.serialize_unit()
+    }
+
+    /// Unit variant is an int of `variant_index`
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Value> {
+        self.serialize_u32(variant_index)
+    }
+
+    /// `RawValue` is decoded
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        match value.serialize(self)? {
+            Value::Bytes(bytes) if name == RAW_VALUE => crate::from_bytes(&bytes),
+            val => Ok(val),
+        }
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(variant(variant_index, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ListSerializer> {
+        Ok(ListSerializer {
+            list: Vec::with_capacity(len.unwrap_or(0)),
+            variant_index: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ListSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<ListSerializer> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<ListSerializer> {
+        Ok(ListSerializer {
+            list: Vec::with_capacity(len),
+            variant_index: Some(variant_index),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer {
+            map: Map::new(),
+            key: None,
+        })
+    }
+
+    /// Bare struct is an `Object` as well
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<ObjectSerializer> {
+        Ok(ObjectSerializer {
+            obj: Map::new(),
+            variant_index: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<ObjectSerializer> {
+        Ok(ObjectSerializer {
+            obj: Map::new(),
+            variant_index: Some(variant_index),
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct ListSerializer {
+    list: Vec<Value>,
+    /// Wrap the list in a variant
+    variant_index: Option<u32>,
+}
+
+impl ser::SerializeSeq for ListSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.list.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        let list = Value::List(self.list);
+        match self.variant_index {
+            Some(variant_index) => Ok(variant(variant_index, list)),
+            None => Ok(list),
+        }
+    }
+}
+
+impl ser::SerializeTuple for ListSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for ListSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for ListSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    map: Map<Value, Value>,
+    key: Option<Value>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        match self.key.take() {
+            Some(key) => {
+                self.map.insert(key, value.serialize(Serializer)?);
+                Ok(())
+            }
+            None => Err(ser::Error::custom("key is missing")),
+        }
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Map(self.map))
+    }
+}
+
+struct ObjectSerializer {
+    obj: Map<u8, Value>,
+    /// Wrap the object in a variant
+    variant_index: Option<u32>,
+}
+
+impl ser::SerializeStruct for ObjectSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let tag = key.parse().map_err(|_| ErrorKind::ErrorFieldTag)?;
+        if self.obj.contains_key(&tag) {
+            return Err(ErrorKind::DuplicateFieldTag.into());
+        }
+        self.obj.insert(tag, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        let obj = Value::Object(self.obj);
+        match self.variant_index {
+            Some(variant_index) => Ok(variant(variant_index, obj)),
+            None => Ok(obj),
+        }
+    }
+}
+
+impl ser::SerializeStructVariant for ObjectSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeStruct::end(self)
+    }
+}
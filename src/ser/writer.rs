@@ -0,0 +1,135 @@
+use std::io;
+
+use crate::types::JceType;
+
+/// Jce encoding on top of any `std::io::Write`
+///
+/// Shared by `Jcebuilder` and `Serializer`, see `Jcebuilder` for the meaning of each method.
+pub(crate) struct JceWriter<W> {
+    writer: W,
+}
+
+impl<W> JceWriter<W>
+where
+    W: io::Write,
+{
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn push_head(&mut self, tag: u8, tp: JceType) -> io::Result<()> {
+        if tag < 15 {
+            self.push_bytes([(tag << 4) + (tp as u8)])
+        } else {
+            self.push_bytes([0xf0 + (tp as u8), tag])
+        }
+    }
+
+    pub fn push_bytes<T>(&mut self, bytes: T) -> io::Result<()>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.writer.write_all(bytes.as_ref())
+    }
+
+    pub fn i8(&mut self, tag: u8, v: i8) -> io::Result<()> {
+        if v == 0 {
+            self.zero(tag)
+        } else {
+            self.push_head(tag, JceType::I8)?;
+            self.push_bytes(v.to_be_bytes())
+        }
+    }
+
+    pub fn i16(&mut self, tag: u8, v: i16) -> io::Result<()> {
+        if i8::MIN as i16 <= v && v <= i8::MAX as i16 {
+            self.i8(tag, v as i8)
+        } else {
+            self.push_head(tag, JceType::I16)?;
+            self.push_bytes(v.to_be_bytes())
+        }
+    }
+
+    pub fn i32(&mut self, tag: u8, v: i32) -> io::Result<()> {
+        if i16::MIN as i32 <= v && v <= i16::MAX as i32 {
+            self.i16(tag, v as i16)
+        } else {
+            self.push_head(tag, JceType::I32)?;
+            self.push_bytes(v.to_be_bytes())
+        }
+    }
+
+    pub fn i64(&mut self, tag: u8, v: i64) -> io::Result<()> {
+        if i32::MIN as i64 <= v && v <= i32::MAX as i64 {
+            self.i32(tag, v as i32)
+        } else {
+            self.push_head(tag, JceType::I64)?;
+            self.push_bytes(v.to_be_bytes())
+        }
+    }
+
+    pub fn f32(&mut self, tag: u8, v: f32) -> io::Result<()> {
+        self.push_head(tag, JceType::F32)?;
+        self.push_bytes(v.to_be_bytes())
+    }
+
+    pub fn f64(&mut self, tag: u8, v: f64) -> io::Result<()> {
+        self.push_head(tag, JceType::F64)?;
+        self.push_bytes(v.to_be_bytes())
+    }
+
+    pub fn str(&mut self, tag: u8, v: &str, max_len: usize) -> io::Result<()> {
+        let v = v.as_bytes();
+        if v.len() <= 255 {
+            self.push_head(tag, JceType::String1)?;
+            self.push_bytes([v.len() as u8])?;
+            self.push_bytes(v)
+        } else {
+            let n = std::cmp::min(v.len(), max_len);
+            self.push_head(tag, JceType::String4)?;
+            self.push_bytes((n as u32).to_be_bytes())?;
+            self.push_bytes(&v[..n])
+        }
+    }
+
+    pub fn map_begin(&mut self, tag: u8, len: i32) -> io::Result<()> {
+        self.push_head(tag, JceType::Map)?;
+        self.i32(0, len)
+    }
+
+    pub fn list_begin(&mut self, tag: u8, len: i32) -> io::Result<()> {
+        self.push_head(tag, JceType::List)?;
+        self.i32(0, len)
+    }
+
+    pub fn struct_begin(&mut self, tag: u8) -> io::Result<()> {
+        self.push_head(tag, JceType::StructBegin)
+    }
+
+    pub fn struct_end(&mut self) -> io::Result<()> {
+        self.push_head(0, JceType::StructEnd)
+    }
+
+    pub fn zero(&mut self, tag: u8) -> io::Result<()> {
+        self.push_head(tag, JceType::Zero)
+    }
+
+    /// Encoded bytes of a value of `tp`, with its head replaced by `tag`
+    pub fn raw(&mut self, tag: u8, tp: JceType, v: &[u8]) -> io::Result<()> {
+        let head = if v[0] >> 4 == 0x0f { 2 } else { 1 };
+        self.push_head(tag, tp)?;
+        self.push_bytes(&v[head..])
+    }
+
+    pub fn bytes(&mut self, tag: u8, v: &[u8], max_len: usize) -> io::Result<()> {
+        let n = std::cmp::min(v.len(), max_len);
+        self.push_head(tag, JceType::Bytes)?;
+        self.push_head(0, JceType::I8)?;
+        self.i32(0, n as i32)?;
+        self.push_bytes(&v[..n])
+    }
+}
@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+
+use bytes::{Buf, BufMut, BytesMut};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio_util::codec::{Decoder, Encoder};
+
+use super::frame::{make_head, parse_head, DEFAULT_MAX_FRAME_LEN, HEAD_LEN};
+use crate::{from_bytes, to_writer, Error, ErrorKind, Result};
+
+/// `tokio_util` codec of Tars frames, requires the `tokio` feature
+///
+/// Decodes each frame into `T`, and encodes anything `Serialize` into a frame.
+/// See `FrameReader` for the frame format.
+///
+/// * `max_frame_len` - The maximum length of a frame, including the length head
+///
+/// # Example
+///
+/// ```
+/// use bytes::BytesMut;
+/// use serde_jce::tars::JceCodec;
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// let mut codec = JceCodec::<i8>::new();
+/// let mut buf = BytesMut::new();
+/// codec.encode(0x12_i8, &mut buf).unwrap();
+/// assert_eq!(&buf[..], [0x00, 0x00, 0x00, 0x06, 0x00, 0x12]);
+/// assert_eq!(codec.decode(&mut buf), Ok(Some(0x12)));
+/// assert_eq!(codec.decode(&mut buf), Ok(None));
+/// ```
+///
+pub struct JceCodec<T> {
+    pub max_frame_len: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> JceCodec<T> {
+    pub fn new() -> Self {
+        Self {
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for JceCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for JceCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            max_frame_len: self.max_frame_len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for JceCodec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JceCodec")
+            .field("max_frame_len", &self.max_frame_len)
+            .finish()
+    }
+}
+
+impl<T> Decoder for JceCodec<T>
+where
+    T: DeserializeOwned,
+{
+    type Item = T;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>> {
+        if src.len() < HEAD_LEN {
+            src.reserve(HEAD_LEN - src.len());
+            return Ok(None);
+        }
+
+        let head = src[..HEAD_LEN].try_into().unwrap();
+        let len = parse_head(head, self.max_frame_len)?;
+        if src.len() < len {
+            src.reserve(len - src.len());
+            return Ok(None);
+        }
+
+        let frame = src.split_to(len);
+        Ok(Some(from_bytes(&frame[HEAD_LEN..])?))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T>> {
+        match self.decode(src)? {
+            Some(item) => Ok(Some(item)),
+            None if src.has_remaining() => Err(ErrorKind::FrameTruncated.into()),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T, U> Encoder<U> for JceCodec<T>
+where
+    U: Serialize,
+{
+    type Error = Error;
+
+    fn encode(&mut self, item: U, dst: &mut BytesMut) -> Result<()> {
+        let start = dst.len();
+        dst.put_slice(&[0; HEAD_LEN]);
+
+        let res = to_writer((&mut *dst).writer(), &item)
+            .and_then(|()| make_head(dst.len() - start - HEAD_LEN, self.max_frame_len));
+        match res {
+            Ok(head) => {
+                dst[start..start + HEAD_LEN].copy_from_slice(&head);
+                Ok(())
+            }
+            Err(err) => {
+                dst.truncate(start);
+                Err(err)
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::io;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{from_bytes, to_writer, ErrorKind, Result};
+
+/// Length of the frame head, a big-endian `u32` of the frame length
+pub(crate) const HEAD_LEN: usize = 4;
+
+/// Default maximum frame length, same as Tars servers
+pub const DEFAULT_MAX_FRAME_LEN: usize = 10 * 1024 * 1024;
+
+/// Check the frame head & return the frame length
+pub(crate) fn parse_head(head: [u8; HEAD_LEN], max_frame_len: usize) -> Result<usize> {
+    let len = u32::from_be_bytes(head) as usize;
+    if len < HEAD_LEN {
+        Err(ErrorKind::FrameTooShort.into())
+    } else if len > max_frame_len {
+        Err(ErrorKind::FrameTooLong.into())
+    } else {
+        Ok(len)
+    }
+}
+
+/// Build the frame head for a body of `body_len`
+pub(crate) fn make_head(body_len: usize, max_frame_len: usize) -> Result<[u8; HEAD_LEN]> {
+    let len = body_len + HEAD_LEN;
+    match u32::try_from(len) {
+        Ok(head) if len <= max_frame_len => Ok(head.to_be_bytes()),
+        _ => Err(ErrorKind::FrameTooLong.into()),
+    }
+}
+
+/// Read Tars frames from a `std::io::Read`
+///
+/// Each frame is a 4-byte big-endian length followed by the Jce body,
+/// the length counts the 4 bytes of itself.
+///
+/// * `max_frame_len` - The maximum length of a frame, including the length head
+///
+/// # Error
+///
+/// * `ErrorKind::FrameTooLong` - the length exceeds `max_frame_len`
+/// * `ErrorKind::FrameTooShort` - the length is less than the length head
+/// * `ErrorKind::FrameTruncated` - the reader ends in the middle of a frame
+///
+/// # Example
+///
+/// ```
+/// use serde_jce::tars::FrameReader;
+///
+/// let bytes = [0x00, 0x00, 0x00, 0x06, 0x00, 0x12, 0x00, 0x00, 0x00, 0x05, 0x0c];
+/// let mut reader = FrameReader::new(&bytes[..]);
+/// assert_eq!(reader.read::<i8>(), Ok(Some(0x12)));
+/// assert_eq!(reader.read::<i8>(), Ok(Some(0)));
+/// assert_eq!(reader.read::<i8>(), Ok(None));
+/// ```
+///
+pub struct FrameReader<R> {
+    pub max_frame_len: usize,
+    reader: R,
+    buf: Vec<u8>,
+}
+
+impl<R> FrameReader<R>
+where
+    R: io::Read,
+{
+    pub fn new(reader: R) -> Self {
+        Self {
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            reader,
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the body of next frame, `None` if the reader ends right at a frame boundary
+    pub fn read_frame(&mut self) -> Result<Option<&[u8]>> {
+        let mut head = [0; HEAD_LEN];
+        let mut filled = 0;
+        while filled < HEAD_LEN {
+            match self.reader.read(&mut head[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(ErrorKind::FrameTruncated.into()),
+                Ok(len) => filled += len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let body_len = parse_head(head, self.max_frame_len)? - HEAD_LEN;
+        self.buf.clear();
+        let mut body = io::Read::take(&mut self.reader, body_len as u64);
+        if io::Read::read_to_end(&mut body, &mut self.buf)? == body_len {
+            Ok(Some(&self.buf))
+        } else {
+            Err(ErrorKind::FrameTruncated.into())
+        }
+    }
+
+    /// Read next frame & deserialize its body, `None` if the reader ends right at a frame boundary
+    pub fn read<T>(&mut self) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        match self.read_frame()? {
+            Some(body) => Ok(Some(from_bytes(body)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Write Tars frames into a `std::io::Write`
+///
+/// See `FrameReader` for the frame format.
+///
+/// * `max_frame_len` - The maximum length of a frame, including the length head
+///
+/// # Example
+///
+/// ```
+/// use serde_jce::tars::FrameWriter;
+///
+/// let mut writer = FrameWriter::new(Vec::new());
+/// writer.write(&0x12_i8).unwrap();
+/// writer.write_frame(&[0x0c]).unwrap();
+/// assert_eq!(
+///     writer.into_inner(),
+///     [0x00, 0x00, 0x00, 0x06, 0x00, 0x12, 0x00, 0x00, 0x00, 0x05, 0x0c]
+/// );
+/// ```
+///
+pub struct FrameWriter<W> {
+    pub max_frame_len: usize,
+    writer: W,
+    buf: Vec<u8>,
+}
+
+impl<W> FrameWriter<W>
+where
+    W: io::Write,
+{
+    pub fn new(writer: W) -> Self {
+        Self {
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            writer,
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Write `body` as a frame
+    pub fn write_frame(&mut self, body: &[u8]) -> Result<()> {
+        let head = make_head(body.len(), self.max_frame_len)?;
+        self.writer.write_all(&head)?;
+        self.writer.write_all(body)?;
+        Ok(())
+    }
+
+    /// Serialize `value` & write it as a frame
+    pub fn write<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.buf.clear();
+        self.buf.extend_from_slice(&[0; HEAD_LEN]);
+        to_writer(&mut self.buf, value)?;
+
+        let head = make_head(self.buf.len() - HEAD_LEN, self.max_frame_len)?;
+        self.buf[..HEAD_LEN].copy_from_slice(&head);
+        self.writer.write_all(&self.buf)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+//! Envelope of Tars RPC
+//!
+//! Every Tars call is a `RequestPacket` answered by a `ResponsePacket`,
+//! the arguments & the result are Jce encoded into `buffer` (`sBuffer`).
+//!
+//! Both packets are bare structs, their fields are not wrapped by `StructBegin` & `StructEnd`.
+//!
+//! A packet of `TUP_VERSION` or `TUP_SIMPLE_VERSION` (UniPacket) carries a `UniAttribute` in `buffer`.
+//!
+//! On a stream, packets are split by `FrameReader` & `FrameWriter`,
+//! or by `JceCodec` with the `tokio` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use serde_jce::tars::RequestPacket;
+//!
+//! #[derive(PartialEq, Debug, Serialize, Deserialize)]
+//! struct Hello {
+//!     #[serde(rename = "0")]
+//!     name: String,
+//! }
+//!
+//! let hello = Hello {
+//!     name: "jce".to_owned(),
+//! };
+//! let mut req = RequestPacket::new("Test.HelloServer.HelloObj", "hello");
+//! req.request_id = 1;
+//! req.set_body(&hello).unwrap();
+//!
+//! let bytes = serde_jce::to_bytes(&req).unwrap();
+//! let req: RequestPacket = serde_jce::from_bytes(&bytes).unwrap();
+//! assert_eq!(req.func_name, "hello");
+//! assert_eq!(req.body::<Hello>().unwrap(), hello);
+//! ```
+//!
+
+#[cfg(feature = "tokio")]
+mod codec;
+mod frame;
+mod packet;
+mod uni;
+
+#[cfg(feature = "tokio")]
+pub use codec::JceCodec;
+pub use frame::{FrameReader, FrameWriter, DEFAULT_MAX_FRAME_LEN};
+pub use packet::{RequestPacket, ResponsePacket};
+pub use uni::UniAttribute;
+
+/// `iVersion` of plain Tars call, arguments are encoded directly into `sBuffer`
+pub const TARS_VERSION: i16 = 1;
+/// `iVersion` of Tup call, `sBuffer` is a `UniAttribute` map of `map<string, map<string, bytes>>`
+pub const TUP_VERSION: i16 = 2;
+/// `iVersion` of simplified Tup call, `sBuffer` is a `UniAttribute` map of `map<string, bytes>`
+pub const TUP_SIMPLE_VERSION: i16 = 3;
+
+/// `cPacketType` of a call that expects a response
+pub const TARS_NORMAL: i8 = 0;
+/// `cPacketType` of a call that expects no response
+pub const TARS_ONEWAY: i8 = 1;
+
+/// `iRet` of a successful call
+pub const TARS_SERVER_SUCCESS: i32 = 0;
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap as Map;
+
+use serde::{Deserialize, Serialize};
+
+use super::{UniAttribute, TARS_NORMAL, TARS_SERVER_SUCCESS, TARS_VERSION};
+use crate::{from_bytes, to_bytes, Result};
+
+/// Request of Tars RPC
+///
+/// Field names follow the Tars IDL without the type prefix, e.g. `iRequestId` is `request_id`.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename = "$serde_jce::bare")]
+pub struct RequestPacket {
+    /// `iVersion`
+    #[serde(rename = "1")]
+    pub version: i16,
+    /// `cPacketType`
+    #[serde(rename = "2")]
+    pub packet_type: i8,
+    /// `iMessageType`
+    #[serde(rename = "3")]
+    pub message_type: i32,
+    /// `iRequestId`
+    #[serde(rename = "4")]
+    pub request_id: i32,
+    /// `sServantName`
+    #[serde(rename = "5")]
+    pub servant_name: String,
+    /// `sFuncName`
+    #[serde(rename = "6")]
+    pub func_name: String,
+    /// `sBuffer`
+    #[serde(rename = "7")]
+    #[serde(with = "serde_bytes")]
+    pub buffer: Vec<u8>,
+    /// `iTimeout`, in milliseconds
+    #[serde(rename = "8")]
+    pub timeout: i32,
+    /// `context`
+    #[serde(rename = "9")]
+    pub context: Map<String, String>,
+    /// `status`
+    #[serde(rename = "10")]
+    pub status: Map<String, String>,
+}
+
+impl RequestPacket {
+    /// Create a `TARS_VERSION` & `TARS_NORMAL` request with empty body
+    pub fn new<S, F>(servant_name: S, func_name: F) -> Self
+    where
+        S: Into<String>,
+        F: Into<String>,
+    {
+        Self {
+            version: TARS_VERSION,
+            packet_type: TARS_NORMAL,
+            servant_name: servant_name.into(),
+            func_name: func_name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Encode `body` at `tag = 0` into `buffer`
+    ///
+    /// For a `TARS_VERSION` call, `body` should be a bare struct of the arguments starting from `tag = 1`
+    pub fn set_body<T>(&mut self, body: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.buffer = to_bytes(body)?;
+        Ok(())
+    }
+
+    /// Decode `buffer` as a value at `tag = 0`
+    pub fn body<'a, T>(&'a self) -> Result<T>
+    where
+        T: Deserialize<'a>,
+    {
+        from_bytes(&self.buffer)
+    }
+
+    /// Encode `attr` into `buffer` & take its version, which makes a UniPacket
+    pub fn set_attribute(&mut self, attr: &UniAttribute) -> Result<()> {
+        self.version = attr.version();
+        self.buffer = attr.encode()?;
+        Ok(())
+    }
+
+    /// Decode `buffer` as `UniAttribute` of `version`
+    pub fn attribute(&self) -> Result<UniAttribute> {
+        UniAttribute::decode(self.version, &self.buffer)
+    }
+}
+
+/// Response of Tars RPC
+///
+/// Field names follow the Tars IDL without the type prefix, e.g. `iRequestId` is `request_id`.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename = "$serde_jce::bare")]
+pub struct ResponsePacket {
+    /// `iVersion`
+    #[serde(rename = "1")]
+    pub version: i16,
+    /// `cPacketType`
+    #[serde(rename = "2")]
+    pub packet_type: i8,
+    /// `iRequestId`
+    #[serde(rename = "3")]
+    pub request_id: i32,
+    /// `iMessageType`
+    #[serde(rename = "4")]
+    pub message_type: i32,
+    /// `iRet`
+    #[serde(rename = "5")]
+    pub ret: i32,
+    /// `sBuffer`
+    #[serde(rename = "6")]
+    #[serde(with = "serde_bytes")]
+    pub buffer: Vec<u8>,
+    /// `status`
+    #[serde(rename = "7")]
+    pub status: Map<String, String>,
+    /// `sResultDesc`, optional
+    #[serde(rename = "8")]
+    #[serde(default)]
+    pub result_desc: String,
+    /// `context`, optional
+    #[serde(rename = "9")]
+    #[serde(default)]
+    pub context: Map<String, String>,
+}
+
+impl ResponsePacket {
+    /// Create a successful response to `req` with empty body
+    pub fn reply_to(req: &RequestPacket) -> Self {
+        Self {
+            version: req.version,
+            packet_type: req.packet_type,
+            request_id: req.request_id,
+            message_type: req.message_type,
+            ret: TARS_SERVER_SUCCESS,
+            context: req.context.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Encode `body` at `tag = 0` into `buffer`
+    ///
+    /// For a `TARS_VERSION` call, `body` should be a bare struct of the return value at `tag = 0` & out arguments
+    pub fn set_body<T>(&mut self, body: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.buffer = to_bytes(body)?;
+        Ok(())
+    }
+
+    /// Decode `buffer` as a value at `tag = 0`
+    pub fn body<'a, T>(&'a self) -> Result<T>
+    where
+        T: Deserialize<'a>,
+    {
+        from_bytes(&self.buffer)
+    }
+
+    /// Encode `attr` into `buffer` & take its version, which makes a UniPacket
+    pub fn set_attribute(&mut self, attr: &UniAttribute) -> Result<()> {
+        self.version = attr.version();
+        self.buffer = attr.encode()?;
+        Ok(())
+    }
+
+    /// Decode `buffer` as `UniAttribute` of `version`
+    pub fn attribute(&self) -> Result<UniAttribute> {
+        UniAttribute::decode(self.version, &self.buffer)
+    }
+}
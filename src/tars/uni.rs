@@ -0,0 +1,153 @@
+use std::collections::BTreeMap as Map;
+
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+use super::{TUP_SIMPLE_VERSION, TUP_VERSION};
+use crate::{from_bytes, to_bytes, to_bytes_with_tag, ErrorKind, Result};
+
+/// Arguments of a UniPacket, which is a packet of `TUP_VERSION` or `TUP_SIMPLE_VERSION`
+///
+/// Every value is Jce encoded at `tag = 0` & stored by name:
+///
+/// * `TUP_VERSION` - `map<string, map<string, bytes>>`, name to type name to value
+/// * `TUP_SIMPLE_VERSION` - `map<string, bytes>`, name to value
+///
+/// Tars implementations of other languages check the type name in `TUP_VERSION`, use `put_with_type` to set it.
+///
+/// # Example
+///
+/// ```
+/// use serde_jce::tars::{RequestPacket, UniAttribute, TUP_SIMPLE_VERSION};
+///
+/// let mut attr = UniAttribute::new(TUP_SIMPLE_VERSION).unwrap();
+/// attr.put("id", &12_i32).unwrap();
+/// attr.put("name", &"jce").unwrap();
+///
+/// let mut req = RequestPacket::new("Test.HelloServer.HelloObj", "hello");
+/// req.set_attribute(&attr).unwrap();
+///
+/// let attr = req.attribute().unwrap();
+/// assert_eq!(attr.get::<i32>("id"), Ok(Some(12)));
+/// assert_eq!(attr.get::<&str>("name"), Ok(Some("jce")));
+/// assert_eq!(attr.get::<i32>("other"), Ok(None));
+/// ```
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct UniAttribute {
+    version: i16,
+    /// `TUP_SIMPLE_VERSION` is stored with an empty type name
+    data: Map<String, Map<String, ByteBuf>>,
+}
+
+impl UniAttribute {
+    /// Create an empty `UniAttribute`
+    ///
+    /// `ErrorKind::UnknownVersion` if `version` is neither `TUP_VERSION` nor `TUP_SIMPLE_VERSION`
+    pub fn new(version: i16) -> Result<Self> {
+        match version {
+            TUP_VERSION | TUP_SIMPLE_VERSION => Ok(Self {
+                version,
+                data: Map::new(),
+            }),
+            _ => Err(ErrorKind::UnknownVersion.into()),
+        }
+    }
+
+    pub fn version(&self) -> i16 {
+        self.version
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.data.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.data.keys().map(String::as_str)
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.data.remove(name).is_some()
+    }
+
+    /// Encode `value` & store it by `name` with an empty type name
+    ///
+    /// Fine for `TUP_SIMPLE_VERSION`, which has no type names. Peers of `TUP_VERSION` usually
+    /// look values up by their Tars type name, e.g. `int32`, and won't find an empty one,
+    /// use `put_with_type` for them.
+    pub fn put<T>(&mut self, name: &str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.put_with_type(name, "", value)
+    }
+
+    /// Encode `value` & store it by `name` with `type_name`, which is ignored in `TUP_SIMPLE_VERSION`
+    pub fn put_with_type<T>(&mut self, name: &str, type_name: &str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let bytes = ByteBuf::from(to_bytes_with_tag(0, value)?);
+        let type_name = match self.version {
+            TUP_VERSION => type_name,
+            _ => "",
+        };
+        self.data
+            .insert(name.to_owned(), Map::from([(type_name.to_owned(), bytes)]));
+        Ok(())
+    }
+
+    /// Decode the value stored by `name`, `None` if there is no such name
+    pub fn get<'a, T>(&'a self, name: &str) -> Result<Option<T>>
+    where
+        T: Deserialize<'a>,
+    {
+        match self.data.get(name).and_then(|types| types.values().next()) {
+            Some(bytes) => Ok(Some(from_bytes(bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Type name of the value stored by `name`, always empty in `TUP_SIMPLE_VERSION`
+    pub fn type_name(&self, name: &str) -> Option<&str> {
+        self.data
+            .get(name)
+            .and_then(|types| types.keys().next())
+            .map(String::as_str)
+    }
+
+    /// Encode as the `buffer` of a packet
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        match self.version {
+            TUP_VERSION => to_bytes(&self.data),
+            _ => {
+                let simple: Map<&String, &ByteBuf> = self
+                    .data
+                    .iter()
+                    .filter_map(|(name, types)| types.values().next().map(|bytes| (name, bytes)))
+                    .collect();
+                to_bytes(&simple)
+            }
+        }
+    }
+
+    /// Decode from the `buffer` of a packet of `version`
+    pub fn decode(version: i16, bytes: &[u8]) -> Result<Self> {
+        let data = match version {
+            TUP_VERSION => from_bytes(bytes)?,
+            TUP_SIMPLE_VERSION => {
+                let simple: Map<String, ByteBuf> = from_bytes(bytes)?;
+                simple
+                    .into_iter()
+                    .map(|(name, bytes)| (name, Map::from([(String::new(), bytes)])))
+                    .collect()
+            }
+            _ => return Err(ErrorKind::UnknownVersion.into()),
+        };
+        Ok(Self { version, data })
+    }
+}
@@ -0,0 +1,520 @@
+use std::collections::BTreeMap as Map;
+use std::fmt::Write;
+
+use crate::{ErrorKind, Result, TypedValue, Value};
+
+////////////////////////////////////////////////////////////////////////////////
+// to text
+
+impl Value {
+    /// Text notation of the value, `Value::from_text` parses it back to the same value
+    ///
+    /// * `zero` - `Zero`
+    /// * `12` - `Int`, a suffix `i8`, `i16`, `i32` or `i64` is allowed when parsing
+    /// * `1.5f32` - `Float`, also `inff32`, `-inff32` & `nanf32`
+    /// * `1.5` - `Double`, also `inf`, `-inf` & `nan`, a suffix `f64` is allowed when parsing
+    /// * `"abc"` - `String`, escaped like a Rust string
+    /// * `bytes"AQID"` - `Bytes` in base64
+    /// * `[1, 2]` - `List`
+    /// * `map{"k": 1}` - `Map`
+    /// * `{0: 12, 1: "abc"}` - `Object`, keys are tags
+    ///
+    /// `//` starts a comment, a trailing comma is allowed.
+    ///
+    /// ```
+    /// use serde_jce::Value;
+    ///
+    /// let val = Value::from_text(r#"{0: 12i8, 1: "abc", 2: [1, 2], 3: bytes"AQID", 4: map{"k": 1}}"#)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     val.to_text(),
+    ///     r#"{0: 12, 1: "abc", 2: [1, 2], 3: bytes"AQID", 4: map{"k": 1}}"#
+    /// );
+    /// assert_eq!(Value::from_text(&val.to_text()), Ok(val));
+    /// ```
+    ///
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        write_value(&mut out, self);
+        out
+    }
+
+    /// Parse the text notation, see `Value::to_text`
+    pub fn from_text(text: &str) -> Result<Value> {
+        Ok(into_value(TypedValue::from_text(text)?))
+    }
+}
+
+impl TypedValue {
+    /// Text notation of the value with its wire types, `TypedValue::from_text` parses it back
+    ///
+    /// The notation of `Value::to_text`, but every int & float has a suffix of its type,
+    /// `string4"abc"` is a `String4`, `{}` is a `Struct` whose fields keep their order.
+    ///
+    /// When parsing, an int without suffix is the narrowest of `I8` to `I64`,
+    /// a float without suffix is `F64`, a string is `String1` unless it is longer than 255 bytes.
+    ///
+    /// ```
+    /// use serde_jce::TypedValue;
+    ///
+    /// let val = TypedValue::from_text(r#"{1: 12i32, 0: string4"abc", 2: zero}"#).unwrap();
+    /// assert_eq!(
+    ///     val,
+    ///     TypedValue::Struct(vec![
+    ///         (1, TypedValue::I32(12)),
+    ///         (0, TypedValue::String4("abc".to_owned())),
+    ///         (2, TypedValue::Zero),
+    ///     ])
+    /// );
+    /// assert_eq!(val.to_text(), r#"{1: 12i32, 0: string4"abc", 2: zero}"#);
+    /// ```
+    ///
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        write_typed(&mut out, self);
+        out
+    }
+
+    /// Parse the text notation, see `TypedValue::to_text`
+    pub fn from_text(text: &str) -> Result<TypedValue> {
+        let mut parser = Parser::new(text);
+        let val = parser.value()?;
+        parser.skip();
+        match parser.peek() {
+            None => Ok(val),
+            Some(_) => error(parser.line, parser.column, "trailing characters"),
+        }
+    }
+}
+
+fn write_value(out: &mut String, val: &Value) {
+    match val {
+        Value::Zero => out.push_str("zero"),
+        Value::Int(v) => {
+            let _ = write!(out, "{}", v);
+        }
+        Value::Float(v) => write_f32(out, *v),
+        Value::Double(v) => write_f64(out, *v, ""),
+        Value::String(v) => write_str(out, "", v),
+        Value::Bytes(v) => write_str(out, "bytes", &base64::encode(v)),
+        Value::List(v) => write_seq(out, "[", "]", v, write_value),
+        Value::Map(v) => write_seq(out, "map{", "}", v, |out, (k, v)| {
+            write_value(out, k);
+            out.push_str(": ");
+            write_value(out, v);
+        }),
+        Value::Object(v) => write_seq(out, "{", "}", v, |out, (tag, v)| {
+            let _ = write!(out, "{}: ", tag);
+            write_value(out, v);
+        }),
+    }
+}
+
+fn write_typed(out: &mut String, val: &TypedValue) {
+    match val {
+        TypedValue::I8(v) => {
+            let _ = write!(out, "{}i8", v);
+        }
+        TypedValue::I16(v) => {
+            let _ = write!(out, "{}i16", v);
+        }
+        TypedValue::I32(v) => {
+            let _ = write!(out, "{}i32", v);
+        }
+        TypedValue::I64(v) => {
+            let _ = write!(out, "{}i64", v);
+        }
+        TypedValue::F32(v) => write_f32(out, *v),
+        TypedValue::F64(v) => write_f64(out, *v, "f64"),
+        TypedValue::String1(v) => write_str(out, "", v),
+        TypedValue::String4(v) => write_str(out, "string4", v),
+        TypedValue::Map(v) => write_seq(out, "map{", "}", v, |out, (k, v)| {
+            write_typed(out, k);
+            out.push_str(": ");
+            write_typed(out, v);
+        }),
+        TypedValue::List(v) => write_seq(out, "[", "]", v, write_typed),
+        TypedValue::Struct(v) => write_seq(out, "{", "}", v, |out, (tag, v)| {
+            let _ = write!(out, "{}: ", tag);
+            write_typed(out, v);
+        }),
+        TypedValue::Zero => out.push_str("zero"),
+        TypedValue::Bytes(v) => write_str(out, "bytes", &base64::encode(v)),
+    }
+}
+
+fn write_seq<T, I>(out: &mut String, begin: &str, end: &str, items: I, f: impl Fn(&mut String, T))
+where
+    I: IntoIterator<Item = T>,
+{
+    out.push_str(begin);
+    for (i, item) in items.into_iter().enumerate() {
+        if i != 0 {
+            out.push_str(", ");
+        }
+        f(out, item);
+    }
+    out.push_str(end);
+}
+
+fn write_str(out: &mut String, prefix: &str, v: &str) {
+    let _ = write!(out, "{}{:?}", prefix, v);
+}
+
+fn write_f32(out: &mut String, v: f32) {
+    if v.is_finite() {
+        let _ = write!(out, "{:?}f32", v);
+    } else {
+        write_special(out, v as f64, "f32");
+    }
+}
+
+fn write_f64(out: &mut String, v: f64, suffix: &str) {
+    if v.is_finite() {
+        let _ = write!(out, "{:?}{}", v, suffix);
+    } else {
+        write_special(out, v, suffix);
+    }
+}
+
+fn write_special(out: &mut String, v: f64, suffix: &str) {
+    let v = if v.is_nan() {
+        "nan"
+    } else if v > 0.0 {
+        "inf"
+    } else {
+        "-inf"
+    };
+    out.push_str(v);
+    out.push_str(suffix);
+}
+
+fn into_value(val: TypedValue) -> Value {
+    match val {
+        TypedValue::I8(v) => Value::Int(v as i64),
+        TypedValue::I16(v) => Value::Int(v as i64),
+        TypedValue::I32(v) => Value::Int(v as i64),
+        TypedValue::I64(v) => Value::Int(v),
+        TypedValue::F32(v) => Value::Float(v),
+        TypedValue::F64(v) => Value::Double(v),
+        TypedValue::String1(v) | TypedValue::String4(v) => Value::String(v),
+        TypedValue::Map(v) => Value::Map(
+            v.into_iter()
+                .map(|(k, v)| (into_value(k), into_value(v)))
+                .collect::<Map<_, _>>(),
+        ),
+        TypedValue::List(v) => Value::List(v.into_iter().map(into_value).collect()),
+        TypedValue::Struct(v) => Value::Object(
+            v.into_iter()
+                .map(|(tag, v)| (tag, into_value(v)))
+                .collect::<Map<_, _>>(),
+        ),
+        TypedValue::Zero => Value::Zero,
+        TypedValue::Bytes(v) => Value::Bytes(v),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// from text
+
+fn error<T>(line: usize, column: usize, message: impl Into<String>) -> Result<T> {
+    Err(ErrorKind::Text {
+        line,
+        column,
+        message: message.into(),
+    }
+    .into())
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Skip whitespaces & comments
+    fn skip(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('/') if self.chars.clone().nth(1) == Some('/') => {
+                    while !matches!(self.bump(), Some('\n') | None) {}
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        self.skip();
+        if self.peek() == Some(c) {
+            self.bump();
+            Ok(())
+        } else {
+            error(self.line, self.column, format!("expected `{}`", c))
+        }
+    }
+
+    fn value(&mut self) -> Result<TypedValue> {
+        self.skip();
+        let (line, column) = (self.line, self.column);
+        match self.peek() {
+            Some('{') => {
+                self.bump();
+                let fields = self.entries(|p| {
+                    let (line, column) = (p.line, p.column);
+                    let tag = match p.word().parse() {
+                        Ok(tag) => tag,
+                        Err(_) => return error(line, column, "expected a tag of 0 to 255"),
+                    };
+                    p.expect(':')?;
+                    Ok((tag, p.value()?))
+                })?;
+                Ok(TypedValue::Struct(fields))
+            }
+            Some('[') => {
+                self.bump();
+                let mut list = Vec::new();
+                loop {
+                    self.skip();
+                    if self.peek() == Some(']') {
+                        self.bump();
+                        return Ok(TypedValue::List(list));
+                    }
+                    list.push(self.value()?);
+                    self.skip();
+                    match self.peek() {
+                        Some(',') => {
+                            self.bump();
+                        }
+                        _ => {
+                            self.expect(']')?;
+                            return Ok(TypedValue::List(list));
+                        }
+                    }
+                }
+            }
+            Some('"') => {
+                let v = self.string()?;
+                if v.len() <= 255 {
+                    Ok(TypedValue::String1(v))
+                } else {
+                    Ok(TypedValue::String4(v))
+                }
+            }
+            Some(c) if c.is_ascii_alphanumeric() || c == '-' || c == '.' => {
+                let word = self.word();
+                match word.as_str() {
+                    "zero" => Ok(TypedValue::Zero),
+                    "map" => {
+                        self.expect('{')?;
+                        let map = self.entries(|p| {
+                            let key = p.value()?;
+                            p.expect(':')?;
+                            Ok((key, p.value()?))
+                        })?;
+                        Ok(TypedValue::Map(map))
+                    }
+                    "bytes" => {
+                        self.skip();
+                        let (line, column) = (self.line, self.column);
+                        match base64::decode(self.string()?) {
+                            Ok(v) => Ok(TypedValue::Bytes(v)),
+                            Err(e) => error(line, column, format!("invalid base64: {}", e)),
+                        }
+                    }
+                    "string4" => {
+                        self.skip();
+                        Ok(TypedValue::String4(self.string()?))
+                    }
+                    _ => number(&word, line, column),
+                }
+            }
+            Some(c) => error(line, column, format!("unexpected `{}`", c)),
+            None => error(line, column, "expected a value"),
+        }
+    }
+
+    /// `k: v, ...}` of a struct or map, the `{` is consumed
+    fn entries<T>(&mut self, mut entry: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let mut entries = Vec::new();
+        loop {
+            self.skip();
+            if self.peek() == Some('}') {
+                self.bump();
+                return Ok(entries);
+            }
+            entries.push(entry(self)?);
+            self.skip();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                _ => {
+                    self.expect('}')?;
+                    return Ok(entries);
+                }
+            }
+        }
+    }
+
+    /// An identifier or a number, e.g. `-1.5e-3f32`
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            let exponent = (c == '-' || c == '+')
+                && matches!(word.chars().last(), Some('e' | 'E'))
+                && !word.contains("0x");
+            if c.is_ascii_alphanumeric()
+                || c == '_'
+                || c == '.'
+                || exponent
+                || word.is_empty() && c == '-'
+            {
+                word.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        word
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let (line, column) = (self.line, self.column);
+        if self.peek() != Some('"') {
+            return error(line, column, "expected a string");
+        }
+        self.bump();
+        let mut str = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(str),
+                Some('\\') => match self.bump() {
+                    Some('n') => str.push('\n'),
+                    Some('t') => str.push('\t'),
+                    Some('r') => str.push('\r'),
+                    Some('0') => str.push('\0'),
+                    Some('u') => {
+                        let (line, column) = (self.line, self.column);
+                        let mut hex = String::new();
+                        if self.bump() != Some('{') {
+                            return error(line, column, "expected `{`");
+                        }
+                        loop {
+                            match self.bump() {
+                                Some('}') => break,
+                                Some(c) => hex.push(c),
+                                None => return error(line, column, "unterminated escape"),
+                            }
+                        }
+                        match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                            Some(c) => str.push(c),
+                            None => return error(line, column, "invalid unicode escape"),
+                        }
+                    }
+                    Some(c) => str.push(c),
+                    None => return error(line, column, "unterminated string"),
+                },
+                Some(c) => str.push(c),
+                None => return error(line, column, "unterminated string"),
+            }
+        }
+    }
+}
+
+/// `12`, `-0x12i16`, `1.5`, `1e3f32`, `nan`, `-inff64`
+fn number(word: &str, line: usize, column: usize) -> Result<TypedValue> {
+    let (neg, body) = match word.strip_prefix('-') {
+        Some(body) => (true, body),
+        None => (false, word),
+    };
+    let hex = body.starts_with("0x");
+    let suffix = ["i16", "i32", "i64", "i8", "f32", "f64"]
+        .iter()
+        .find(|s| body.len() > s.len() && body.ends_with(*s) && !(hex && s.starts_with('f')));
+    let body = match suffix {
+        Some(s) => &body[..body.len() - s.len()],
+        None => body,
+    };
+    let sign = if neg { "-" } else { "" };
+    let invalid = || error(line, column, format!("invalid number `{}`", word));
+
+    let float = matches!(body, "inf" | "nan")
+        || !hex && body.contains(|c| matches!(c, '.' | 'e' | 'E'))
+        || matches!(suffix, Some(s) if s.starts_with('f'));
+    if float {
+        let text = format!("{}{}", sign, body);
+        return match suffix.copied() {
+            Some("f32") => match text.parse() {
+                Ok(v) => Ok(TypedValue::F32(v)),
+                Err(_) => invalid(),
+            },
+            Some("f64") | None => match text.parse() {
+                Ok(v) => Ok(TypedValue::F64(v)),
+                Err(_) => invalid(),
+            },
+            Some(_) => invalid(),
+        };
+    }
+
+    let v = match body.strip_prefix("0x") {
+        Some(digits) => i64::from_str_radix(&format!("{}{}", sign, digits), 16),
+        None if body.starts_with(|c: char| c.is_ascii_digit()) => {
+            format!("{}{}", sign, body).parse()
+        }
+        None => return error(line, column, format!("unexpected `{}`", word)),
+    };
+    let v = match v {
+        Ok(v) => v,
+        Err(_) => return invalid(),
+    };
+    let out_of_range = || error(line, column, format!("`{}` is out of range", word));
+    match suffix.copied() {
+        Some("i8") => v.try_into().map(TypedValue::I8).or_else(|_| out_of_range()),
+        Some("i16") => v
+            .try_into()
+            .map(TypedValue::I16)
+            .or_else(|_| out_of_range()),
+        Some("i32") => v
+            .try_into()
+            .map(TypedValue::I32)
+            .or_else(|_| out_of_range()),
+        Some("i64") => Ok(TypedValue::I64(v)),
+        _ => Ok(if let Ok(v) = v.try_into() {
+            TypedValue::I8(v)
+        } else if let Ok(v) = v.try_into() {
+            TypedValue::I16(v)
+        } else if let Ok(v) = v.try_into() {
+            TypedValue::I32(v)
+        } else {
+            TypedValue::I64(v)
+        }),
+    }
+}
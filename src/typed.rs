@@ -0,0 +1,301 @@
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::de::ANY;
+use crate::ser::JceWriter;
+use crate::types::RAW_VALUE;
+use crate::{Error, ErrorKind, JceParser, JceType, RawValue, Result, SliceRead};
+
+/// A dynamic value that remembers the wire type of every node
+///
+/// Unlike `Value`, nothing is narrowed or merged: `I8(0)` is not `Zero`,
+/// `String1` is not `String4`, and entries of maps & structs keep the order of the input.
+/// Serializing a decoded `TypedValue` reproduces the input byte-for-byte,
+/// as long as the input writes lengths & heads in the shortest form
+/// with elements of list at tag 0, keys of map at tag 0 & values at tag 1, as every Jce encoder does.
+///
+/// Only meaningful with `serde_jce`'s `Deserializer` & `Serializer`.
+///
+/// ```
+/// use serde_jce::TypedValue;
+///
+/// // `{ 0: 0x12_i16, 2: "ab" }` with fields in reverse order
+/// let bytes = [0x0a, 0x26, 0x02, 0x61, 0x62, 0x01, 0x00, 0x12, 0x0b];
+/// let val: TypedValue = serde_jce::from_bytes(&bytes).unwrap();
+/// assert_eq!(
+///     val,
+///     TypedValue::Struct(vec![
+///         (2, TypedValue::String1("ab".to_owned())),
+///         (0, TypedValue::I16(0x12)),
+///     ])
+/// );
+/// assert_eq!(serde_jce::to_bytes(&val), Ok(bytes.to_vec()));
+/// ```
+///
+#[derive(Clone)]
+pub enum TypedValue {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    String1(String),
+    String4(String),
+    Map(Vec<(TypedValue, TypedValue)>),
+    List(Vec<TypedValue>),
+    Struct(Vec<(u8, TypedValue)>),
+    Zero,
+    Bytes(Vec<u8>),
+}
+
+impl TypedValue {
+    /// Type of its head, `StructBegin` for `Struct`
+    pub fn jce_type(&self) -> JceType {
+        match self {
+            TypedValue::I8(_) => JceType::I8,
+            TypedValue::I16(_) => JceType::I16,
+            TypedValue::I32(_) => JceType::I32,
+            TypedValue::I64(_) => JceType::I64,
+            TypedValue::F32(_) => JceType::F32,
+            TypedValue::F64(_) => JceType::F64,
+            TypedValue::String1(_) => JceType::String1,
+            TypedValue::String4(_) => JceType::String4,
+            TypedValue::Map(_) => JceType::Map,
+            TypedValue::List(_) => JceType::List,
+            TypedValue::Struct(_) => JceType::StructBegin,
+            TypedValue::Zero => JceType::Zero,
+            TypedValue::Bytes(_) => JceType::Bytes,
+        }
+    }
+
+    fn parse<'de>(parser: &mut JceParser<'de, SliceRead<'de>>) -> Result<Self> {
+        let val = match parser.pick_type()? {
+            JceType::I8 => TypedValue::I8(parser.i8()?),
+            JceType::I16 => TypedValue::I16(parser.i16()?),
+            JceType::I32 => TypedValue::I32(parser.i32()?),
+            JceType::I64 => TypedValue::I64(parser.i64()?),
+            JceType::F32 => TypedValue::F32(parser.f32()?),
+            JceType::F64 => TypedValue::F64(parser.f64()?),
+            JceType::String1 => TypedValue::String1(parser.str_small()?.to_owned()),
+            JceType::String4 => TypedValue::String4(parser.str_big()?.to_owned()),
+            JceType::Map => {
+                let len = parser.map()?;
+                let mut map = Vec::new();
+                for _ in 0..len {
+                    map.push((Self::parse(parser)?, Self::parse(parser)?));
+                }
+                TypedValue::Map(map)
+            }
+            JceType::List => {
+                let len = parser.list()?;
+                let mut list = Vec::new();
+                for _ in 0..len {
+                    list.push(Self::parse(parser)?);
+                }
+                TypedValue::List(list)
+            }
+            JceType::StructBegin => {
+                parser.struct_begin()?;
+                let mut fields = Vec::new();
+                loop {
+                    let (tag, tp) = parser.pick_head()?;
+                    if tp == JceType::StructEnd {
+                        break;
+                    }
+                    fields.push((tag, Self::parse(parser)?));
+                }
+                parser.struct_end()?;
+                TypedValue::Struct(fields)
+            }
+            JceType::Zero => {
+                parser.zero()?;
+                TypedValue::Zero
+            }
+            JceType::Bytes => TypedValue::Bytes(parser.bytes()?.to_vec()),
+            JceType::StructEnd => {
+                let err: Error = ErrorKind::WrongType {
+                    expected: ANY,
+                    actual: JceType::StructEnd,
+                }
+                .into();
+                return Err(err.with_offset(parser.position()));
+            }
+        };
+        Ok(val)
+    }
+
+    fn write(&self, writer: &mut JceWriter<Vec<u8>>, tag: u8) -> Result<()> {
+        writer.push_head(tag, self.jce_type())?;
+        match self {
+            TypedValue::I8(v) => writer.push_bytes(v.to_be_bytes())?,
+            TypedValue::I16(v) => writer.push_bytes(v.to_be_bytes())?,
+            TypedValue::I32(v) => writer.push_bytes(v.to_be_bytes())?,
+            TypedValue::I64(v) => writer.push_bytes(v.to_be_bytes())?,
+            TypedValue::F32(v) => writer.push_bytes(v.to_be_bytes())?,
+            TypedValue::F64(v) => writer.push_bytes(v.to_be_bytes())?,
+            TypedValue::String1(v) => {
+                let len: u8 = v.len().try_into().map_err(|_| ErrorKind::StringTooLong)?;
+                writer.push_bytes([len])?;
+                writer.push_bytes(v)?;
+            }
+            TypedValue::String4(v) => {
+                let len: u32 = v.len().try_into().map_err(|_| ErrorKind::StringTooLong)?;
+                writer.push_bytes(len.to_be_bytes())?;
+                writer.push_bytes(v)?;
+            }
+            TypedValue::Map(v) => {
+                writer.i32(0, length(v.len(), ErrorKind::MapTooLong)?)?;
+                for (k, v) in v {
+                    k.write(writer, 0)?;
+                    v.write(writer, 1)?;
+                }
+            }
+            TypedValue::List(v) => {
+                writer.i32(0, length(v.len(), ErrorKind::SeqTooLong)?)?;
+                for e in v {
+                    e.write(writer, 0)?;
+                }
+            }
+            TypedValue::Struct(v) => {
+                for (tag, v) in v {
+                    v.write(writer, *tag)?;
+                }
+                writer.struct_end()?;
+            }
+            TypedValue::Zero => {}
+            TypedValue::Bytes(v) => {
+                writer.push_head(0, JceType::I8)?;
+                writer.i32(0, length(v.len(), ErrorKind::BytesTooLong)?)?;
+                writer.push_bytes(v)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn length(len: usize, err: ErrorKind) -> Result<i32> {
+    len.try_into().map_err(|_| err.into())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl shortcut from
+
+macro_rules! typed_from {
+    ($($ty:ty => $variant:ident)*) => {
+        $(
+            impl From<$ty> for TypedValue {
+                fn from(v: $ty) -> Self {
+                    TypedValue::$variant(v)
+                }
+            }
+        )*
+    };
+}
+
+typed_from!(
+    i8 => I8
+    i16 => I16
+    i32 => I32
+    i64 => I64
+    f32 => F32
+    f64 => F64
+    Vec<u8> => Bytes
+    Vec<TypedValue> => List
+);
+
+/// `String1` unless it is longer than 255 bytes
+impl From<String> for TypedValue {
+    fn from(v: String) -> Self {
+        if v.len() <= u8::MAX as usize {
+            TypedValue::String1(v)
+        } else {
+            TypedValue::String4(v)
+        }
+    }
+}
+
+impl From<&str> for TypedValue {
+    fn from(v: &str) -> Self {
+        TypedValue::from(v.to_owned())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl fmt
+
+impl fmt::Debug for TypedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypedValue::I8(v) => f.write_fmt(format_args!("{}i8", v)),
+            TypedValue::I16(v) => f.write_fmt(format_args!("{}i16", v)),
+            TypedValue::I32(v) => f.write_fmt(format_args!("{}i32", v)),
+            TypedValue::I64(v) => f.write_fmt(format_args!("{}i64", v)),
+            TypedValue::F32(v) => f.write_fmt(format_args!("{}f32", v)),
+            TypedValue::F64(v) => f.write_fmt(format_args!("{}f64", v)),
+            TypedValue::String1(v) => f.debug_tuple("String1").field(v).finish(),
+            TypedValue::String4(v) => f.debug_tuple("String4").field(v).finish(),
+            TypedValue::Map(v) => f.debug_tuple("Map").field(v).finish(),
+            TypedValue::List(v) => v.fmt(f),
+            TypedValue::Struct(v) => f.debug_tuple("Struct").field(v).finish(),
+            TypedValue::Zero => f.write_str("Zero"),
+            TypedValue::Bytes(v) => f.write_fmt(format_args!("Bytes({})", &base64::encode(v))),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl PartialEq
+
+impl PartialEq for TypedValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TypedValue::I8(lhs), TypedValue::I8(rhs)) => lhs == rhs,
+            (TypedValue::I16(lhs), TypedValue::I16(rhs)) => lhs == rhs,
+            (TypedValue::I32(lhs), TypedValue::I32(rhs)) => lhs == rhs,
+            (TypedValue::I64(lhs), TypedValue::I64(rhs)) => lhs == rhs,
+            (TypedValue::F32(lhs), TypedValue::F32(rhs)) => lhs.to_bits() == rhs.to_bits(),
+            (TypedValue::F64(lhs), TypedValue::F64(rhs)) => lhs.to_bits() == rhs.to_bits(),
+            (TypedValue::String1(lhs), TypedValue::String1(rhs)) => lhs == rhs,
+            (TypedValue::String4(lhs), TypedValue::String4(rhs)) => lhs == rhs,
+            (TypedValue::Map(lhs), TypedValue::Map(rhs)) => lhs == rhs,
+            (TypedValue::List(lhs), TypedValue::List(rhs)) => lhs == rhs,
+            (TypedValue::Struct(lhs), TypedValue::Struct(rhs)) => lhs == rhs,
+            (TypedValue::Zero, TypedValue::Zero) => true,
+            (TypedValue::Bytes(lhs), TypedValue::Bytes(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TypedValue {}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl Serialize & Deserialize
+
+/// Written as a `RawValue`, so the serializer can't narrow anything
+impl Serialize for TypedValue {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut writer = JceWriter::new(Vec::new());
+        self.write(&mut writer, 0)
+            .map_err(serde::ser::Error::custom)?;
+        let bytes = writer.into_inner();
+        serializer.serialize_newtype_struct(RAW_VALUE, serde_bytes::Bytes::new(&bytes))
+    }
+}
+
+/// Captured as a `RawValue`, then parsed
+impl<'de> Deserialize<'de> for TypedValue {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawValue::deserialize(deserializer)?;
+        let mut parser = JceParser::from_bytes(raw.get());
+        Self::parse(&mut parser).map_err(serde::de::Error::custom)
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{Error, Result};
+use crate::{Error, ErrorKind, Result};
+
+/// Struct name that marks a struct as bare, see `## bare struct` in crate document
+pub(crate) const BARE_STRUCT: &str = "$serde_jce::bare";
+
+/// Newtype name of `RawValue`
+pub(crate) const RAW_VALUE: &str = "$serde_jce::RawValue";
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 #[repr(u8)]
@@ -38,7 +44,7 @@ impl TryFrom<u8> for JceType {
             11 => Ok(JceType::StructEnd),
             12 => Ok(JceType::Zero),
             13 => Ok(JceType::Bytes),
-            _ => Err(Error::UnknownJceType),
+            _ => Err(ErrorKind::UnknownJceType.into()),
         }
     }
 }
@@ -1,11 +1,16 @@
 use std::cmp::Ordering;
 use std::collections::BTreeMap as Map;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use serde::de::{Error, MapAccess, SeqAccess, Visitor};
 use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::de::{BYTES, F32, F64, I64, STR};
+use crate::{ErrorKind, JceType};
+
+#[derive(Clone)]
 pub enum Value {
     Zero,
     Int(i64),
@@ -21,12 +26,40 @@ pub enum Value {
 ////////////////////////////////////////////////////////////////////////////////
 // impl shortcut from
 
-impl From<i64> for Value {
-    fn from(v: i64) -> Self {
-        Value::Int(v)
-    }
+macro_rules! value_from_int {
+    ($($ty:ty)*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(v: $ty) -> Self {
+                    Value::Int(v as i64)
+                }
+            }
+        )*
+    };
+}
+
+value_from_int!(bool i8 i16 i32 i64 u8 u16 u32);
+
+/// `ErrorKind::IntTooBig` if it doesn't fit in `i64`
+macro_rules! value_try_from_int {
+    ($($ty:ty)*) => {
+        $(
+            impl TryFrom<$ty> for Value {
+                type Error = crate::Error;
+
+                fn try_from(v: $ty) -> crate::Result<Self> {
+                    match i64::try_from(v) {
+                        Ok(v) => Ok(Value::Int(v)),
+                        Err(_) => Err(crate::ErrorKind::IntTooBig.into()),
+                    }
+                }
+            }
+        )*
+    };
 }
 
+value_try_from_int!(isize usize u64 i128 u128);
+
 impl From<f32> for Value {
     fn from(v: f32) -> Self {
         Value::Float(v)
@@ -51,6 +84,174 @@ impl From<String> for Value {
     }
 }
 
+impl From<Vec<u8>> for Value {
+    fn from(v: Vec<u8>) -> Self {
+        Value::Bytes(v)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(v: Vec<Value>) -> Self {
+        Value::List(v)
+    }
+}
+
+impl From<Map<Value, Value>> for Value {
+    fn from(v: Map<Value, Value>) -> Self {
+        Value::Map(v)
+    }
+}
+
+impl From<Map<u8, Value>> for Value {
+    fn from(v: Map<u8, Value>) -> Self {
+        Value::Object(v)
+    }
+}
+
+impl From<char> for Value {
+    fn from(v: char) -> Self {
+        Value::String(v.to_string())
+    }
+}
+
+impl From<&[u8]> for Value {
+    fn from(v: &[u8]) -> Self {
+        Value::Bytes(v.to_owned())
+    }
+}
+
+/// `None` is `Zero`
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(v: Option<T>) -> Self {
+        match v {
+            Some(v) => v.into(),
+            None => Value::Zero,
+        }
+    }
+}
+
+impl Default for Value {
+    fn default() -> Self {
+        Value::Zero
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl TryFrom<&Value>
+//
+// Read like the `Deserializer` does: `Zero` is `0`, `""` or empty,
+// `ErrorKind::WrongType` for another type, `ErrorKind::IntTooBig` if the int doesn't fit
+
+macro_rules! int_try_from_value {
+    ($($ty:ty)*) => {
+        $(
+            impl TryFrom<&Value> for $ty {
+                type Error = crate::Error;
+
+                fn try_from(v: &Value) -> crate::Result<Self> {
+                    let v = match v {
+                        Value::Zero => 0,
+                        Value::Int(v) => *v,
+                        v => return Err(v.wrong_type(I64)),
+                    };
+                    <$ty>::try_from(v).map_err(|_| ErrorKind::IntTooBig.into())
+                }
+            }
+        )*
+    };
+}
+
+int_try_from_value!(i8 i16 i32 u8 u16 u32 u64 isize usize);
+
+impl TryFrom<&Value> for i64 {
+    type Error = crate::Error;
+
+    fn try_from(v: &Value) -> crate::Result<Self> {
+        match v {
+            Value::Zero => Ok(0),
+            Value::Int(v) => Ok(*v),
+            v => Err(v.wrong_type(I64)),
+        }
+    }
+}
+
+/// An int of `i8`, like the `Deserializer` does
+impl TryFrom<&Value> for bool {
+    type Error = crate::Error;
+
+    fn try_from(v: &Value) -> crate::Result<Self> {
+        Ok(i8::try_from(v)? != 0)
+    }
+}
+
+impl TryFrom<&Value> for f32 {
+    type Error = crate::Error;
+
+    fn try_from(v: &Value) -> crate::Result<Self> {
+        match v {
+            Value::Zero => Ok(0.0),
+            Value::Float(v) => Ok(*v),
+            v => Err(v.wrong_type(F32)),
+        }
+    }
+}
+
+impl TryFrom<&Value> for f64 {
+    type Error = crate::Error;
+
+    fn try_from(v: &Value) -> crate::Result<Self> {
+        match v {
+            Value::Zero => Ok(0.0),
+            Value::Float(v) => Ok(*v as f64),
+            Value::Double(v) => Ok(*v),
+            v => Err(v.wrong_type(F64)),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for &'a str {
+    type Error = crate::Error;
+
+    fn try_from(v: &'a Value) -> crate::Result<Self> {
+        match v {
+            Value::Zero => Ok(""),
+            Value::String(v) => Ok(v),
+            v => Err(v.wrong_type(STR)),
+        }
+    }
+}
+
+impl TryFrom<&Value> for String {
+    type Error = crate::Error;
+
+    fn try_from(v: &Value) -> crate::Result<Self> {
+        <&str>::try_from(v).map(str::to_owned)
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for &'a [u8] {
+    type Error = crate::Error;
+
+    fn try_from(v: &'a Value) -> crate::Result<Self> {
+        match v {
+            Value::Zero => Ok(&[]),
+            Value::Bytes(v) => Ok(v),
+            v => Err(v.wrong_type(BYTES)),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Vec<u8> {
+    type Error = crate::Error;
+
+    fn try_from(v: &Value) -> crate::Result<Self> {
+        <&[u8]>::try_from(v).map(<[u8]>::to_vec)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // impl shortcut getter
 
@@ -99,6 +300,80 @@ impl Value {
     value_getter!(obj_mut, Value::Object, &mut Self, &mut Map<u8,Value>);
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// impl accessor & predicate
+
+/// Range-checked accessor by `TryFrom<&Value>`, `None` for another type or an int that doesn't fit
+macro_rules! value_as {
+    ($func:ident, $ret:ty) => {
+        pub fn $func(&self) -> Option<$ret> {
+            <$ret>::try_from(self).ok()
+        }
+    };
+}
+
+macro_rules! value_is {
+    ($func:ident, $pat:pat) => {
+        pub fn $func(&self) -> bool {
+            matches!(self, $pat)
+        }
+    };
+}
+
+impl Value {
+    value_as!(as_bool, bool);
+    value_as!(as_i8, i8);
+    value_as!(as_i16, i16);
+    value_as!(as_i32, i32);
+    value_as!(as_i64, i64);
+    value_as!(as_u8, u8);
+    value_as!(as_u16, u16);
+    value_as!(as_u32, u32);
+    value_as!(as_u64, u64);
+    value_as!(as_f32, f32);
+    value_as!(as_f64, f64);
+    value_as!(as_str, &str);
+    value_as!(as_bytes, &[u8]);
+
+    value_is!(is_zero, Value::Zero);
+    value_is!(is_int, Value::Int(_));
+    value_is!(is_float, Value::Float(_));
+    value_is!(is_double, Value::Double(_));
+    value_is!(is_string, Value::String(_));
+    value_is!(is_bytes, Value::Bytes(_));
+    value_is!(is_list, Value::List(_));
+    value_is!(is_map, Value::Map(_));
+    value_is!(is_object, Value::Object(_));
+
+    /// Type of the head it is written with, ints are narrowed & `0` is `Zero` like the writer does
+    pub fn jce_type(&self) -> JceType {
+        match self {
+            Value::Zero => JceType::Zero,
+            Value::Int(0) => JceType::Zero,
+            Value::Int(v) if *v as i8 as i64 == *v => JceType::I8,
+            Value::Int(v) if *v as i16 as i64 == *v => JceType::I16,
+            Value::Int(v) if *v as i32 as i64 == *v => JceType::I32,
+            Value::Int(_) => JceType::I64,
+            Value::Float(_) => JceType::F32,
+            Value::Double(_) => JceType::F64,
+            Value::String(v) if v.len() <= u8::MAX as usize => JceType::String1,
+            Value::String(_) => JceType::String4,
+            Value::Bytes(_) => JceType::Bytes,
+            Value::List(_) => JceType::List,
+            Value::Map(_) => JceType::Map,
+            Value::Object(_) => JceType::StructBegin,
+        }
+    }
+
+    pub(crate) fn wrong_type(&self, expected: &'static [JceType]) -> crate::Error {
+        ErrorKind::WrongType {
+            expected,
+            actual: self.jce_type(),
+        }
+        .into()
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // impl fmt
 
@@ -124,10 +399,7 @@ impl fmt::Debug for Value {
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         match self {
-            Value::Zero => match other {
-                Value::Zero => true,
-                _ => false,
-            },
+            Value::Zero => matches!(other, Value::Zero),
             Value::Int(lhs) => match other {
                 Value::Int(rhs) => lhs == rhs,
                 _ => false,
@@ -231,9 +503,50 @@ impl Ord for Value {
     }
 }
 
+/// Consistent with `PartialEq`, floats are hashed by bits
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Zero => {}
+            Value::Int(v) => v.hash(state),
+            Value::Float(v) => v.to_bits().hash(state),
+            Value::Double(v) => v.to_bits().hash(state),
+            Value::String(v) => v.hash(state),
+            Value::Bytes(v) => v.hash(state),
+            Value::List(v) => v.hash(state),
+            Value::Map(v) => v.hash(state),
+            Value::Object(v) => v.hash(state),
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // impl Serialize
 
+// stupid dirty trick, thanks to serde
+pub(crate) const STR_TABLE: [&str; 256] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16",
+    "17", "18", "19", "20", "21", "22", "23", "24", "25", "26", "27", "28", "29", "30", "31", "32",
+    "33", "34", "35", "36", "37", "38", "39", "40", "41", "42", "43", "44", "45", "46", "47", "48",
+    "49", "50", "51", "52", "53", "54", "55", "56", "57", "58", "59", "60", "61", "62", "63", "64",
+    "65", "66", "67", "68", "69", "70", "71", "72", "73", "74", "75", "76", "77", "78", "79", "80",
+    "81", "82", "83", "84", "85", "86", "87", "88", "89", "90", "91", "92", "93", "94", "95", "96",
+    "97", "98", "99", "100", "101", "102", "103", "104", "105", "106", "107", "108", "109", "110",
+    "111", "112", "113", "114", "115", "116", "117", "118", "119", "120", "121", "122", "123",
+    "124", "125", "126", "127", "128", "129", "130", "131", "132", "133", "134", "135", "136",
+    "137", "138", "139", "140", "141", "142", "143", "144", "145", "146", "147", "148", "149",
+    "150", "151", "152", "153", "154", "155", "156", "157", "158", "159", "160", "161", "162",
+    "163", "164", "165", "166", "167", "168", "169", "170", "171", "172", "173", "174", "175",
+    "176", "177", "178", "179", "180", "181", "182", "183", "184", "185", "186", "187", "188",
+    "189", "190", "191", "192", "193", "194", "195", "196", "197", "198", "199", "200", "201",
+    "202", "203", "204", "205", "206", "207", "208", "209", "210", "211", "212", "213", "214",
+    "215", "216", "217", "218", "219", "220", "221", "222", "223", "224", "225", "226", "227",
+    "228", "229", "230", "231", "232", "233", "234", "235", "236", "237", "238", "239", "240",
+    "241", "242", "243", "244", "245", "246", "247", "248", "249", "250", "251", "252", "253",
+    "254", "255",
+];
+
 impl Serialize for Value {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -261,31 +574,6 @@ impl Serialize for Value {
                 seq.end()
             }
             Value::Object(v) => {
-                // stupid dirty trick, thanks to serde
-                const STR_TABLE: [&'static str; 256] = [
-                    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14",
-                    "15", "16", "17", "18", "19", "20", "21", "22", "23", "24", "25", "26", "27",
-                    "28", "29", "30", "31", "32", "33", "34", "35", "36", "37", "38", "39", "40",
-                    "41", "42", "43", "44", "45", "46", "47", "48", "49", "50", "51", "52", "53",
-                    "54", "55", "56", "57", "58", "59", "60", "61", "62", "63", "64", "65", "66",
-                    "67", "68", "69", "70", "71", "72", "73", "74", "75", "76", "77", "78", "79",
-                    "80", "81", "82", "83", "84", "85", "86", "87", "88", "89", "90", "91", "92",
-                    "93", "94", "95", "96", "97", "98", "99", "100", "101", "102", "103", "104",
-                    "105", "106", "107", "108", "109", "110", "111", "112", "113", "114", "115",
-                    "116", "117", "118", "119", "120", "121", "122", "123", "124", "125", "126",
-                    "127", "128", "129", "130", "131", "132", "133", "134", "135", "136", "137",
-                    "138", "139", "140", "141", "142", "143", "144", "145", "146", "147", "148",
-                    "149", "150", "151", "152", "153", "154", "155", "156", "157", "158", "159",
-                    "160", "161", "162", "163", "164", "165", "166", "167", "168", "169", "170",
-                    "171", "172", "173", "174", "175", "176", "177", "178", "179", "180", "181",
-                    "182", "183", "184", "185", "186", "187", "188", "189", "190", "191", "192",
-                    "193", "194", "195", "196", "197", "198", "199", "200", "201", "202", "203",
-                    "204", "205", "206", "207", "208", "209", "210", "211", "212", "213", "214",
-                    "215", "216", "217", "218", "219", "220", "221", "222", "223", "224", "225",
-                    "226", "227", "228", "229", "230", "231", "232", "233", "234", "235", "236",
-                    "237", "238", "239", "240", "241", "242", "243", "244", "245", "246", "247",
-                    "248", "249", "250", "251", "252", "253", "254", "255",
-                ];
                 let mut seq = serializer.serialize_struct("Value", v.len())?;
                 for (k, v) in v {
                     seq.serialize_field(STR_TABLE[*k as usize], v)?;
@@ -340,7 +628,7 @@ impl<'de> Visitor<'de> for ValueVisitor {
     where
         E: Error,
     {
-        Ok(Value::Int(value as i64))
+        Ok(Value::Int(value))
     }
 
     fn visit_f32<E>(self, value: f32) -> Result<Self::Value, E>
@@ -0,0 +1,345 @@
+use std::fmt;
+
+use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::value::STR_TABLE;
+use crate::Value;
+
+/// Borrowed version of `Value`
+///
+/// `Str` & `Bytes` borrow from the input, containers keep their entries in `Vec`s of pairs
+/// in the order of the input. Only a slice input can be borrowed,
+/// deserializing it from a `std::io::Read` fails on strings & bytes.
+///
+/// ```
+/// use serde_jce::{Value, ValueRef};
+///
+/// // `[ "ab", 0x12 ]`
+/// let bytes = [0x09, 0x00, 0x02, 0x06, 0x02, 0x61, 0x62, 0x00, 0x12];
+/// let val: ValueRef = serde_jce::from_bytes(&bytes).unwrap();
+/// assert_eq!(val, ValueRef::List(vec![ValueRef::Str("ab"), ValueRef::Int(0x12)]));
+/// assert_eq!(
+///     val.to_owned(),
+///     Value::List(vec![Value::String("ab".to_owned()), Value::Int(0x12)])
+/// );
+/// ```
+///
+#[derive(Clone)]
+pub enum ValueRef<'de> {
+    Zero,
+    Int(i64),
+    Float(f32),
+    Double(f64),
+    Str(&'de str),
+    Bytes(&'de [u8]),
+    List(Vec<ValueRef<'de>>),
+    Map(Vec<(ValueRef<'de>, ValueRef<'de>)>),
+    Object(Vec<(u8, ValueRef<'de>)>),
+}
+
+impl<'de> ValueRef<'de> {
+    /// Copy into a `Value`, later entries of a duplicate key win
+    pub fn to_owned(&self) -> Value {
+        match self {
+            ValueRef::Zero => Value::Zero,
+            ValueRef::Int(v) => Value::Int(*v),
+            ValueRef::Float(v) => Value::Float(*v),
+            ValueRef::Double(v) => Value::Double(*v),
+            ValueRef::Str(v) => Value::String((*v).to_owned()),
+            ValueRef::Bytes(v) => Value::Bytes(v.to_vec()),
+            ValueRef::List(v) => Value::List(v.iter().map(ValueRef::to_owned).collect()),
+            ValueRef::Map(v) => Value::Map(
+                v.iter()
+                    .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                    .collect(),
+            ),
+            ValueRef::Object(v) => {
+                Value::Object(v.iter().map(|(k, v)| (*k, v.to_owned())).collect())
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl shortcut getter
+
+macro_rules! value_getter {
+    ($func:ident, $enum:path, $arg:ty, $ret:ty) => {
+        pub fn $func(self: $arg) -> Option<$ret> {
+            match self {
+                $enum(v) => Some(v),
+                _ => None,
+            }
+        }
+    };
+}
+
+impl<'de> ValueRef<'de> {
+    value_getter!(int, ValueRef::Int, Self, i64);
+    value_getter!(int_ref, ValueRef::Int, &Self, &i64);
+    value_getter!(int_mut, ValueRef::Int, &mut Self, &mut i64);
+
+    value_getter!(float, ValueRef::Float, Self, f32);
+    value_getter!(float_ref, ValueRef::Float, &Self, &f32);
+    value_getter!(float_mut, ValueRef::Float, &mut Self, &mut f32);
+
+    value_getter!(double, ValueRef::Double, Self, f64);
+    value_getter!(double_ref, ValueRef::Double, &Self, &f64);
+    value_getter!(double_mut, ValueRef::Double, &mut Self, &mut f64);
+
+    value_getter!(string, ValueRef::Str, Self, &'de str);
+    value_getter!(string_ref, ValueRef::Str, &Self, &&'de str);
+    value_getter!(string_mut, ValueRef::Str, &mut Self, &mut &'de str);
+
+    value_getter!(bytes, ValueRef::Bytes, Self, &'de [u8]);
+    value_getter!(bytes_ref, ValueRef::Bytes, &Self, &&'de [u8]);
+    value_getter!(bytes_mut, ValueRef::Bytes, &mut Self, &mut &'de [u8]);
+
+    value_getter!(list, ValueRef::List, Self, Vec<ValueRef<'de>>);
+    value_getter!(list_ref, ValueRef::List, &Self, &Vec<ValueRef<'de>>);
+    value_getter!(list_mut, ValueRef::List, &mut Self, &mut Vec<ValueRef<'de>>);
+
+    value_getter!(
+        map,
+        ValueRef::Map,
+        Self,
+        Vec<(ValueRef<'de>, ValueRef<'de>)>
+    );
+    value_getter!(
+        map_ref,
+        ValueRef::Map,
+        &Self,
+        &Vec<(ValueRef<'de>, ValueRef<'de>)>
+    );
+    value_getter!(
+        map_mut,
+        ValueRef::Map,
+        &mut Self,
+        &mut Vec<(ValueRef<'de>, ValueRef<'de>)>
+    );
+
+    value_getter!(obj, ValueRef::Object, Self, Vec<(u8, ValueRef<'de>)>);
+    value_getter!(obj_ref, ValueRef::Object, &Self, &Vec<(u8, ValueRef<'de>)>);
+    value_getter!(
+        obj_mut,
+        ValueRef::Object,
+        &mut Self,
+        &mut Vec<(u8, ValueRef<'de>)>
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl fmt
+
+impl<'de> fmt::Debug for ValueRef<'de> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValueRef::Zero => f.write_str("Zero"),
+            ValueRef::Int(v) => v.fmt(f),
+            ValueRef::Float(v) => f.write_fmt(format_args!("{}f32", v)),
+            ValueRef::Double(v) => f.write_fmt(format_args!("{}f64", v)),
+            ValueRef::Str(v) => v.fmt(f),
+            ValueRef::Bytes(v) => f.write_fmt(format_args!("Bytes({})", &base64::encode(v))),
+            ValueRef::List(v) => v.fmt(f),
+            ValueRef::Map(v) => f
+                .debug_map()
+                .entries(v.iter().map(|(k, v)| (k, v)))
+                .finish(),
+            ValueRef::Object(v) => f.debug_tuple("Object").field(&DebugObject(v)).finish(),
+        }
+    }
+}
+
+struct DebugObject<'a, 'de>(&'a [(u8, ValueRef<'de>)]);
+
+impl<'a, 'de> fmt::Debug for DebugObject<'a, 'de> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(k, v)| (k, v)))
+            .finish()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl PartialEq
+
+impl<'de> PartialEq for ValueRef<'de> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ValueRef::Zero, ValueRef::Zero) => true,
+            (ValueRef::Int(lhs), ValueRef::Int(rhs)) => lhs == rhs,
+            (ValueRef::Float(lhs), ValueRef::Float(rhs)) => lhs.to_bits() == rhs.to_bits(),
+            (ValueRef::Double(lhs), ValueRef::Double(rhs)) => lhs.to_bits() == rhs.to_bits(),
+            (ValueRef::Str(lhs), ValueRef::Str(rhs)) => lhs == rhs,
+            (ValueRef::Bytes(lhs), ValueRef::Bytes(rhs)) => lhs == rhs,
+            (ValueRef::List(lhs), ValueRef::List(rhs)) => lhs == rhs,
+            (ValueRef::Map(lhs), ValueRef::Map(rhs)) => lhs == rhs,
+            (ValueRef::Object(lhs), ValueRef::Object(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl<'de> Eq for ValueRef<'de> {}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl Serialize
+
+impl<'de> Serialize for ValueRef<'de> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ValueRef::Zero => serializer.serialize_none(),
+            ValueRef::Int(v) => serializer.serialize_i64(*v),
+            ValueRef::Float(v) => serializer.serialize_f32(*v),
+            ValueRef::Double(v) => serializer.serialize_f64(*v),
+            ValueRef::Str(v) => serializer.serialize_str(v),
+            ValueRef::Bytes(v) => serializer.serialize_bytes(v),
+            ValueRef::List(v) => {
+                let mut seq = serializer.serialize_seq(Some(v.len()))?;
+                for e in v {
+                    seq.serialize_element(e)?;
+                }
+                seq.end()
+            }
+            ValueRef::Map(v) => {
+                let mut seq = serializer.serialize_map(Some(v.len()))?;
+                for (k, v) in v {
+                    seq.serialize_entry(k, v)?;
+                }
+                seq.end()
+            }
+            ValueRef::Object(v) => {
+                let mut seq = serializer.serialize_struct("Value", v.len())?;
+                for (k, v) in v {
+                    seq.serialize_field(STR_TABLE[*k as usize], v)?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// impl Deserialize
+
+struct ValueRefVisitor;
+
+impl<'de> Visitor<'de> for ValueRefVisitor {
+    type Value = ValueRef<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a borrowed jce encoded object")
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Zero)
+    }
+
+    fn visit_i8<E>(self, value: i8) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Int(value as i64))
+    }
+
+    fn visit_i16<E>(self, value: i16) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Int(value as i64))
+    }
+
+    fn visit_i32<E>(self, value: i32) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Int(value as i64))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Int(value))
+    }
+
+    fn visit_f32<E>(self, value: f32) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Float(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Double(value))
+    }
+
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Str(value))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ValueRef::Bytes(v))
+    }
+
+    fn visit_seq<A>(self, mut acc: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vec = Vec::new();
+        while let Some(value) = acc.next_element()? {
+            vec.push(value);
+        }
+        Ok(ValueRef::List(vec))
+    }
+
+    fn visit_map<A>(self, mut acc: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match acc.size_hint() {
+            // Object
+            None => {
+                let mut vec = Vec::new();
+                while let Some(entry) = acc.next_entry::<u8, ValueRef>()? {
+                    vec.push(entry);
+                }
+                Ok(ValueRef::Object(vec))
+            }
+            // Map
+            Some(_) => {
+                let mut vec = Vec::new();
+                while let Some(entry) = acc.next_entry()? {
+                    vec.push(entry);
+                }
+                Ok(ValueRef::Map(vec))
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ValueRef<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<ValueRef<'de>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueRefVisitor)
+    }
+}
@@ -52,10 +52,7 @@ fn str_long() {
     let mut builder = Jcebuilder::new();
     let str = "\x7f".repeat(300);
     builder.str(0, str);
-    let expected: Vec<u8> = hex!("07 0000012c")
-        .into_iter()
-        .chain([0x7f; 300].into_iter())
-        .collect();
+    let expected: Vec<u8> = hex!("07 0000012c").into_iter().chain([0x7f; 300]).collect();
     assert_eq!(builder.done(), expected);
 }
 
@@ -0,0 +1,139 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Run `jce` with `stdin`, return the exit code & stdout
+fn jce(args: &[&str], stdin: &str) -> (i32, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_jce"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn decode() {
+    let hex = "0a 00 12 16 02 6162 0b";
+    assert_eq!(
+        jce(&["decode", "--from", "hex"], hex),
+        (
+            0,
+            concat!(
+                "     0  0: StructBegin\n",
+                "     1    0: I8 = 18\n",
+                "     3    1: String1[2] = \"ab\"\n",
+                "     7  StructEnd\n",
+            )
+            .to_owned()
+        )
+    );
+    assert_eq!(
+        jce(&["decode", "--from", "hex", "--to", "json"], hex),
+        (0, "{\n  \"0\": 18,\n  \"1\": \"ab\"\n}\n".to_owned())
+    );
+    assert_eq!(
+        jce(
+            &["decode", "--from", "base64", "--to", "text"],
+            "CgASFgJhYgs="
+        ),
+        (0, "{0: 18i8, 1: \"ab\"}\n".to_owned())
+    );
+    assert_eq!(
+        jce(
+            &["decode", "--from", "hex", "--to", "text", "--bare"],
+            "00 12 10 13"
+        ),
+        (0, "{0: 18i8, 1: 19i8}\n".to_owned())
+    );
+    assert_eq!(jce(&["decode", "--from", "hex"], "0a 00").0, 0);
+    assert_eq!(
+        jce(&["decode", "--from", "hex", "--to", "json"], "0a 00").0,
+        1
+    );
+}
+
+#[test]
+fn invalid_hex() {
+    assert_eq!(
+        jce(&["decode", "--from", "hex"], "0a 00 1 0b"),
+        (1, String::new())
+    );
+    assert_eq!(jce(&["decode", "--from", "hex"], "001"), (1, String::new()));
+    assert_eq!(
+        jce(&["decode", "--from", "hex"], "00+1"),
+        (1, String::new())
+    );
+    assert_eq!(
+        jce(&["validate", "--from", "hex"], "+0"),
+        (1, String::new())
+    );
+}
+
+#[test]
+fn encode() {
+    assert_eq!(
+        jce(&["encode"], "{0: 18i8, 1: \"ab\"}"),
+        (0, "0a0012160261620b\n".to_owned())
+    );
+    assert_eq!(
+        jce(
+            &["encode", "--from", "json", "--to", "base64", "--bare"],
+            "{\"0\": 18, \"1\": \"ab\"}"
+        ),
+        (0, "ABIWAmFi\n".to_owned())
+    );
+    assert_eq!(jce(&["encode"], "{0: 300i8}").0, 1);
+}
+
+#[test]
+fn validate() {
+    assert_eq!(
+        jce(&["validate", "--from", "hex"], "00 12 16 02 6162"),
+        (0, "ok: 6 bytes\n".to_owned())
+    );
+    assert_eq!(
+        jce(&["validate", "--from", "hex"], "00 12 16 02 61"),
+        (1, "invalid: NotEnoughtBytes, offset 2\n".to_owned())
+    );
+}
+
+#[test]
+fn diff() {
+    let dir = std::env::temp_dir();
+    let a = dir.join("serde_jce_cli_diff_a.hex");
+    let b = dir.join("serde_jce_cli_diff_b.hex");
+    std::fs::write(&a, "0a 00 12 19 0001 00 01 0b").unwrap();
+    std::fs::write(&b, "0a 00 13 19 0001 00 01 2c 0b").unwrap();
+    let (a, b) = (a.to_str().unwrap(), b.to_str().unwrap());
+    assert_eq!(
+        jce(&["diff", "--from", "hex", a, b], ""),
+        (1, "- .0: 18\n+ .0: 19\n+ .2: zero\n".to_owned())
+    );
+    assert_eq!(
+        jce(&["diff", "--from", "hex", a, a], ""),
+        (0, String::new())
+    );
+}
+
+#[test]
+fn usage() {
+    assert_eq!(jce(&["decode", "--foo"], "").0, 2);
+    assert_eq!(jce(&["diff", "a"], "").0, 2);
+    assert_eq!(jce(&["frobnicate"], ""), (2, String::new()));
+    assert!(jce(&[], "").1.starts_with("Usage: jce"));
+    assert!(jce(&["help"], "").1.starts_with("Usage: jce"));
+}
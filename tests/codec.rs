@@ -0,0 +1,140 @@
+#![cfg(feature = "tokio")]
+
+use bytes::BytesMut;
+use futures::{SinkExt, StreamExt};
+use hex_literal::hex;
+use serde::{Deserialize, Serialize};
+use serde_jce::tars::{JceCodec, RequestPacket, ResponsePacket};
+use serde_jce::{Error, ErrorKind};
+use tokio::io::AsyncWriteExt;
+use tokio_util::codec::{Decoder, Encoder, Framed, FramedRead};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Hello {
+    #[serde(rename = "0")]
+    name: String,
+}
+
+#[test]
+fn decode_partial() {
+    let mut codec = JceCodec::<Hello>::new();
+    let bytes = hex!("00000009 0a 06 01 61 0b" "0000");
+    let mut buf = BytesMut::new();
+    for byte in &bytes[..9] {
+        assert_eq!(codec.decode(&mut buf), Ok(None));
+        buf.extend_from_slice(&[*byte]);
+    }
+    assert_eq!(
+        codec.decode(&mut buf),
+        Ok(Some(Hello {
+            name: "a".to_owned()
+        }))
+    );
+    buf.extend_from_slice(&bytes[9..]);
+    assert_eq!(codec.decode(&mut buf), Ok(None));
+    assert_eq!(
+        codec.decode_eof(&mut buf).map_err(Error::into_kind),
+        Err(ErrorKind::FrameTruncated)
+    );
+}
+
+#[test]
+fn encode() {
+    let mut codec = JceCodec::<Hello>::new();
+    let mut buf = BytesMut::new();
+    let hello = Hello {
+        name: "a".to_owned(),
+    };
+    assert_eq!(codec.encode(&hello, &mut buf), Ok(()));
+    assert_eq!(codec.encode(hello, &mut buf), Ok(()));
+    assert_eq!(
+        &buf[..],
+        hex!("00000009 0a 06 01 61 0b" "00000009 0a 06 01 61 0b")
+    );
+}
+
+#[test]
+fn frame_too_long() {
+    let mut codec = JceCodec::<Hello>::new();
+    codec.max_frame_len = 8;
+    let mut buf = BytesMut::from(&hex!("0c")[..]);
+    let hello = Hello {
+        name: "a".to_owned(),
+    };
+    assert_eq!(
+        codec.encode(&hello, &mut buf).map_err(Error::into_kind),
+        Err(ErrorKind::FrameTooLong)
+    );
+    assert_eq!(&buf[..], hex!("0c"));
+
+    let mut buf = BytesMut::from(&hex!("00000009 0a 06 01 61 0b")[..]);
+    assert_eq!(
+        codec.decode(&mut buf).map_err(Error::into_kind),
+        Err(ErrorKind::FrameTooLong)
+    );
+}
+
+#[tokio::test]
+async fn duplex() {
+    let (client, server) = tokio::io::duplex(16);
+    let mut client = Framed::new(client, JceCodec::<ResponsePacket>::new());
+    let mut server = Framed::new(server, JceCodec::<RequestPacket>::new());
+
+    let mut req = RequestPacket::new("Test.HelloServer.HelloObj", "hello");
+    req.request_id = 1;
+    req.set_body(&Hello {
+        name: "jce".to_owned(),
+    })
+    .unwrap();
+
+    let task = tokio::spawn(async move {
+        while let Some(req) = server.next().await {
+            let req = req.unwrap();
+            let hello: Hello = req.body().unwrap();
+            let mut rsp = ResponsePacket::reply_to(&req);
+            rsp.set_body(&Hello {
+                name: hello.name + "!",
+            })
+            .unwrap();
+            server.send(rsp).await.unwrap();
+        }
+    });
+
+    for _ in 0..3 {
+        client.send(&req).await.unwrap();
+        let rsp = client.next().await.unwrap().unwrap();
+        assert_eq!(rsp.request_id, 1);
+        assert_eq!(
+            rsp.body::<Hello>(),
+            Ok(Hello {
+                name: "jce!".to_owned()
+            })
+        );
+    }
+
+    drop(client);
+    task.await.unwrap();
+}
+
+#[tokio::test]
+async fn truncated_stream() {
+    let (mut writer, reader) = tokio::io::duplex(16);
+    let mut reader = FramedRead::new(reader, JceCodec::<Hello>::new());
+
+    writer
+        .write_all(&hex!("00000009 0a 06 01 61 0b" "00000009 0a"))
+        .await
+        .unwrap();
+    drop(writer);
+
+    assert_eq!(
+        reader.next().await,
+        Some(Ok(Hello {
+            name: "a".to_owned()
+        }))
+    );
+    assert_eq!(
+        reader.next().await.map(|r| r.map_err(Error::into_kind)),
+        Some(Err(ErrorKind::FrameTruncated))
+    );
+}
@@ -99,7 +99,7 @@ de_test!(
     &serde_bytes::Bytes,
     serde_bytes::Bytes::new(&[0x00, 0x00])
 );
-de_test!(list_zero, hex!("09 0c"), Vec<i8>, Vec::new());
+de_test!(list_zero, hex!("09 0c"), Vec<i8>, Vec::<i8>::new());
 de_test!(
     map_zero,
     hex!("08 0c"),
@@ -118,13 +118,41 @@ fn extra_bytes() {
 
 #[test]
 fn struct_tag_error() {
-    #[derive(PartialEq, Debug, Deserialize)]
-    struct Test {
-        #[serde(rename = "0")]
-        v0: i8,
-        #[serde(rename = "0")]
-        v1: i16,
+    // `#[derive(Deserialize)]` can't name two fields alike without an
+    // unreachable match arm, so ask for the duplicate tags by hand
+    #[derive(PartialEq, Debug)]
+    struct Test;
+
+    impl<'de> Deserialize<'de> for Test {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct TestVisitor;
+
+            impl<'de> serde::de::Visitor<'de> for TestVisitor {
+                type Value = Test;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    formatter.write_str("struct Test")
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Test, A::Error>
+                where
+                    A: serde::de::MapAccess<'de>,
+                {
+                    while map
+                        .next_entry::<serde::de::IgnoredAny, serde::de::IgnoredAny>()?
+                        .is_some()
+                    {}
+                    Ok(Test)
+                }
+            }
+
+            deserializer.deserialize_struct("Test", &["0", "0"], TestVisitor)
+        }
     }
+
     let bytes = &hex!("0a 0001 1002 0b");
     let res: serde_jce::Result<Test> = serde_jce::from_bytes(bytes);
     assert!(res.is_err());
@@ -144,6 +172,64 @@ fn data_tag_error() {
     assert!(res.is_err());
 }
 
+#[test]
+fn missing_field() {
+    #[derive(PartialEq, Debug, Deserialize)]
+    struct Outer {
+        #[serde(rename = "0")]
+        v0: i8,
+        #[serde(rename = "1")]
+        inner: Inner,
+    }
+    #[derive(PartialEq, Debug, Deserialize)]
+    struct Inner {
+        #[serde(rename = "0")]
+        v0: i8,
+        #[serde(rename = "2")]
+        v2: i8,
+    }
+    assert_eq!(
+        serde_jce::from_bytes::<Outer>(&hex!("0a 1a 0001 2002 0b 0b"))
+            .map_err(serde_jce::Error::into_kind),
+        Err(serde_jce::ErrorKind::MissingField {
+            tag: 0,
+            name: "Outer"
+        })
+    );
+    assert_eq!(
+        serde_jce::from_bytes::<Outer>(&hex!("0a 0001 1a 0001 0b 0b"))
+            .map_err(serde_jce::Error::into_kind),
+        Err(serde_jce::ErrorKind::MissingField {
+            tag: 2,
+            name: "Inner"
+        })
+    );
+}
+
+#[test]
+fn missing_optional_field() {
+    #[derive(PartialEq, Debug, Deserialize)]
+    struct Test {
+        #[serde(rename = "0")]
+        v0: i8,
+        #[serde(rename = "1")]
+        v1: Option<i8>,
+        #[serde(rename = "2", default = "default_v2")]
+        v2: i8,
+    }
+    fn default_v2() -> i8 {
+        5
+    }
+    assert_eq!(
+        serde_jce::from_bytes(&hex!("0a 0001 0b")),
+        Ok(Test {
+            v0: 1,
+            v1: None,
+            v2: 5
+        })
+    );
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // struct test
 
@@ -257,3 +343,239 @@ fn whole_struct() {
     };
     assert_eq!(val, expected);
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// reader test
+
+/// Hand out at most one byte per `read`, like a slow socket
+struct ByteByByte<'a>(&'a [u8]);
+
+impl<'a> std::io::Read for ByteByByte<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((byte, rest)), Some(out)) => {
+                *out = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Deserialize)]
+struct ReaderTest {
+    #[serde(rename = "0")]
+    v0: i8,
+    #[serde(rename = "1")]
+    v1: String,
+    #[serde(rename = "2")]
+    #[serde(with = "serde_bytes")]
+    v2: Vec<u8>,
+    #[serde(rename = "3")]
+    v3: std::collections::BTreeMap<String, i16>,
+}
+
+const READER_TEST_BYTES: [u8; 29] = hex!(
+    "0a"
+    "00 12"
+    "16 04 31323334"
+    "2d 00 0004 12345678"
+    "38 0001 06 03 6b6579 11 1234"
+    "0b"
+);
+
+fn reader_test_value() -> ReaderTest {
+    ReaderTest {
+        v0: 0x12,
+        v1: "1234".to_owned(),
+        v2: hex!("12345678").to_vec(),
+        v3: std::collections::BTreeMap::from([("key".to_owned(), 0x1234)]),
+    }
+}
+
+#[test]
+fn reader() {
+    let val: ReaderTest = serde_jce::from_reader(&READER_TEST_BYTES[..]).unwrap();
+    assert_eq!(val, reader_test_value());
+}
+
+#[test]
+fn reader_partial_read() {
+    let val: ReaderTest = serde_jce::from_reader(ByteByByte(&READER_TEST_BYTES)).unwrap();
+    assert_eq!(val, reader_test_value());
+}
+
+#[test]
+fn reader_stream() {
+    let bytes = [READER_TEST_BYTES, READER_TEST_BYTES].concat();
+    let mut de = serde_jce::Deserializer::from_reader(ByteByByte(&bytes));
+    for _ in 0..2 {
+        let val = ReaderTest::deserialize(&mut de).unwrap();
+        assert_eq!(val, reader_test_value());
+    }
+    assert_eq!(de.end(), Ok(()));
+}
+
+#[test]
+fn reader_unexpected_eof() {
+    for len in 0..READER_TEST_BYTES.len() {
+        let res: serde_jce::Result<ReaderTest> = serde_jce::from_reader(&READER_TEST_BYTES[..len]);
+        assert_eq!(
+            res.map_err(serde_jce::Error::into_kind),
+            Err(serde_jce::ErrorKind::UnexpectedEof)
+        );
+    }
+}
+
+#[test]
+fn reader_extra_bytes() {
+    let res: serde_jce::Result<u64> = serde_jce::from_reader(&hex!("00 12 34")[..]);
+    assert_eq!(
+        res.map_err(serde_jce::Error::into_kind),
+        Err(serde_jce::ErrorKind::TrailingBytes)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// enum test
+
+#[derive(PartialEq, Debug, Deserialize)]
+enum Enum {
+    Unit,
+    Other,
+    Newtype(i16),
+    Tuple(i8, String),
+    Struct {
+        #[serde(rename = "0")]
+        v0: i8,
+        #[serde(rename = "2")]
+        v2: i8,
+    },
+}
+
+de_test!(enum_unit_first, hex!("0c"), Enum, Enum::Unit);
+de_test!(enum_unit, hex!("00 01"), Enum, Enum::Other);
+de_test!(enum_unit_i32, hex!("02 00000001"), Enum, Enum::Other);
+de_test!(
+    enum_newtype,
+    hex!("0a 0002 11 1234 0b"),
+    Enum,
+    Enum::Newtype(0x1234)
+);
+de_test!(
+    enum_tuple,
+    hex!("0a 0003 19 0002 0012 06 01 61 0b"),
+    Enum,
+    Enum::Tuple(0x12, "a".to_owned())
+);
+de_test!(
+    enum_struct,
+    hex!("0a 0004 1a 0012 2034 0b 0b"),
+    Enum,
+    Enum::Struct { v0: 0x12, v2: 0x34 }
+);
+de_test!(
+    enum_unknown_field,
+    hex!("0a 0002 11 1234 26 01 61 0b"),
+    Enum,
+    Enum::Newtype(0x1234)
+);
+
+#[test]
+fn enum_in_struct() {
+    #[derive(PartialEq, Debug, Deserialize)]
+    struct Test {
+        #[serde(rename = "1")]
+        v1: Enum,
+        #[serde(rename = "2")]
+        v2: Enum,
+    }
+    let bytes = hex!("0a 1001 2a 0002 1012 0b 0b");
+    let expected = Test {
+        v1: Enum::Other,
+        v2: Enum::Newtype(0x12),
+    };
+    assert_eq!(serde_jce::from_bytes(&bytes), Ok(expected));
+}
+
+#[test]
+fn enum_error() {
+    // unknown variant
+    assert!(serde_jce::from_bytes::<Enum>(&hex!("00 05")).is_err());
+    assert!(serde_jce::from_bytes::<Enum>(&hex!("00 ff")).is_err());
+    // data variant without wrapper
+    assert_eq!(
+        serde_jce::from_bytes::<Enum>(&hex!("00 02")).map_err(serde_jce::Error::into_kind),
+        Err(serde_jce::ErrorKind::WrongType {
+            expected: &[serde_jce::JceType::StructBegin],
+            actual: serde_jce::JceType::I8,
+        })
+    );
+    // data at wrong tag
+    assert_eq!(
+        serde_jce::from_bytes::<Enum>(&hex!("0a 0002 21 1234 0b"))
+            .map_err(serde_jce::Error::into_kind),
+        Err(serde_jce::ErrorKind::ErrorFieldTag)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// error location
+
+#[derive(PartialEq, Debug, Deserialize)]
+struct LocationInner {
+    #[serde(rename = "1")]
+    v1: i8,
+}
+
+#[derive(PartialEq, Debug, Deserialize)]
+struct LocationOuter {
+    #[serde(rename = "0")]
+    v0: Vec<std::collections::BTreeMap<String, LocationInner>>,
+}
+
+#[test]
+fn error_location() {
+    // `{ 0: [{}, { "key": { 1: "a" } }] }`
+    let bytes = hex!("0a 09 0002 08 0c 08 0001 06 03 6b6579 1a 16 01 61 0b 0b");
+    let err = serde_jce::from_bytes::<LocationOuter>(&bytes).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &serde_jce::ErrorKind::WrongType {
+            expected: &[serde_jce::JceType::Zero, serde_jce::JceType::I8],
+            actual: serde_jce::JceType::String1,
+        }
+    );
+    assert_eq!(err.offset(), Some(15));
+    assert_eq!(err.path(), ".0[1].key.1");
+    assert_eq!(
+        err.to_string(),
+        "expected type Zero or I8, found String1 at .0[1].key.1, offset 15"
+    );
+
+    let err = serde_jce::from_reader::<_, LocationOuter>(&bytes[..]).unwrap_err();
+    assert_eq!(err.offset(), Some(15));
+    assert_eq!(err.path(), ".0[1].key.1");
+}
+
+#[test]
+fn error_location_of_value() {
+    // error of visitor is located at the start of the value
+    let bytes = hex!("08 0001 00 05 11 012c");
+    let err = serde_jce::from_bytes::<std::collections::BTreeMap<i8, u8>>(&bytes).unwrap_err();
+    assert!(matches!(err.kind(), serde_jce::ErrorKind::Message(_)));
+    assert_eq!(err.offset(), Some(5));
+    assert_eq!(err.path(), "[5]");
+
+    // truncated input
+    let err = serde_jce::from_bytes::<Vec<i32>>(&hex!("09 0002 02 0000")).unwrap_err();
+    assert_eq!(err.kind(), &serde_jce::ErrorKind::NotEnoughtBytes);
+    assert_eq!(err.offset(), Some(3));
+    assert_eq!(err.path(), "[0]");
+
+    let err = serde_jce::from_bytes::<i8>(&hex!("00 12 34")).unwrap_err();
+    assert_eq!(err.kind(), &serde_jce::ErrorKind::TrailingBytes);
+    assert_eq!(err.offset(), Some(2));
+    assert_eq!(err.path(), "");
+}
@@ -0,0 +1,199 @@
+use hex_literal::hex;
+use serde::{Deserialize, Serialize};
+use serde_jce::{from_bytes, to_bytes, Error, ErrorKind, JceType};
+use serde_jce_derive::Jce;
+
+#[derive(PartialEq, Debug, Default, Jce)]
+struct Tagged {
+    #[jce(tag = 0)]
+    v0: i8,
+    #[jce(tag = 1)]
+    v1: String,
+    #[jce(tag = 20)]
+    v20: Vec<i16>,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Renamed {
+    #[serde(rename = "0")]
+    v0: i8,
+    #[serde(rename = "1")]
+    v1: String,
+    #[serde(rename = "20")]
+    v20: Vec<i16>,
+}
+
+#[test]
+fn same_as_rename() {
+    let tagged = Tagged {
+        v0: 0x12,
+        v1: "abc".to_owned(),
+        v20: vec![1, 0x1234],
+    };
+    let renamed = Renamed {
+        v0: 0x12,
+        v1: "abc".to_owned(),
+        v20: vec![1, 0x1234],
+    };
+    let bytes = to_bytes(&renamed).unwrap();
+    assert_eq!(to_bytes(&tagged), Ok(bytes.clone()));
+    assert_eq!(from_bytes(&bytes), Ok(tagged));
+}
+
+#[derive(PartialEq, Debug, Jce)]
+struct Fields {
+    #[jce(tag = 0, required)]
+    required: i32,
+    #[jce(tag = 1)]
+    optional: i32,
+    #[jce(tag = 2, default = 5)]
+    number: i64,
+    #[jce(tag = 3, default = "jce")]
+    string: String,
+    #[jce(tag = 4, default = vec![1, 2])]
+    list: Vec<i8>,
+    #[jce(skip)]
+    skipped: bool,
+}
+
+#[test]
+fn default_value() {
+    assert_eq!(
+        from_bytes(&hex!("0a 00 12 0b")),
+        Ok(Fields {
+            required: 0x12,
+            optional: 0,
+            number: 5,
+            string: "jce".to_owned(),
+            list: vec![1, 2],
+            skipped: false,
+        })
+    );
+}
+
+#[test]
+fn bare_default() {
+    #[derive(PartialEq, Debug, Jce)]
+    struct Test {
+        #[jce(tag = 0, default)]
+        v0: String,
+    }
+    assert_eq!(from_bytes(&hex!("0a 0b")), Ok(Test { v0: String::new() }));
+}
+
+#[test]
+fn present_value() {
+    let val = Fields {
+        required: 0x12,
+        optional: 0x34,
+        number: 6,
+        string: "abc".to_owned(),
+        list: vec![],
+        skipped: true,
+    };
+    let bytes = hex!("0a 00 12 10 34 20 06 36 03 616263 49 0c 0b");
+    assert_eq!(to_bytes(&val), Ok(bytes.to_vec()));
+    assert_eq!(
+        from_bytes(&bytes),
+        Ok(Fields {
+            skipped: false,
+            ..val
+        })
+    );
+}
+
+#[test]
+fn missing_required() {
+    assert_eq!(
+        from_bytes::<Fields>(&hex!("0a 10 34 0b")).map_err(Error::into_kind),
+        Err(ErrorKind::MissingField {
+            tag: 0,
+            name: "Fields"
+        })
+    );
+}
+
+#[derive(PartialEq, Debug, Jce)]
+struct RequiredOption {
+    #[jce(tag = 0, required)]
+    required: Option<i8>,
+    #[jce(tag = 1)]
+    optional: Option<i8>,
+}
+
+#[test]
+fn required_option() {
+    assert_eq!(
+        from_bytes(&hex!("0a 00 12 0b")),
+        Ok(RequiredOption {
+            required: Some(0x12),
+            optional: None,
+        })
+    );
+    assert_eq!(
+        from_bytes::<RequiredOption>(&hex!("0a 10 12 0b")).map_err(Error::into_kind),
+        Err(ErrorKind::MissingField {
+            tag: 0,
+            name: "RequiredOption"
+        })
+    );
+}
+
+#[derive(PartialEq, Debug, Jce)]
+#[jce(bare)]
+struct Bare<'a, T> {
+    #[jce(tag = 1)]
+    name: &'a str,
+    #[jce(tag = 2)]
+    #[serde(with = "serde_bytes")]
+    bytes: Vec<u8>,
+    #[jce(tag = 3)]
+    inner: T,
+}
+
+#[test]
+fn bare_generic() {
+    let val = Bare {
+        name: "a",
+        bytes: vec![1, 2],
+        inner: Tagged {
+            v0: 1,
+            v1: "b".to_owned(),
+            v20: vec![],
+        },
+    };
+    let bytes = hex!(
+        "16 01 61"
+        "2d 00 00 02 0102"
+        "3a 00 01 16 01 62 f9 14 0c 0b"
+    );
+    assert_eq!(to_bytes(&val), Ok(bytes.to_vec()));
+    assert_eq!(from_bytes(&bytes), Ok(val));
+}
+
+#[test]
+fn wrong_type() {
+    assert_eq!(
+        from_bytes::<Tagged>(&hex!("0a 06 01 61 0b")).map_err(Error::into_kind),
+        Err(ErrorKind::WrongType {
+            expected: &[JceType::Zero, JceType::I8],
+            actual: JceType::String1,
+        })
+    );
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Jce)]
+enum Color {
+    Red = 1,
+    Green,
+    Blue = 0x1234,
+}
+
+#[test]
+fn enum_discriminant() {
+    assert_eq!(to_bytes(&Color::Red), Ok(hex!("00 01").to_vec()));
+    assert_eq!(to_bytes(&Color::Green), Ok(hex!("00 02").to_vec()));
+    assert_eq!(to_bytes(&Color::Blue), Ok(hex!("01 1234").to_vec()));
+    assert_eq!(from_bytes(&hex!("01 1234")), Ok(Color::Blue));
+    assert!(from_bytes::<Color>(&hex!("00 03")).is_err());
+}
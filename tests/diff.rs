@@ -0,0 +1,109 @@
+use std::collections::BTreeMap as Map;
+
+use serde_jce::{Change, ChangeKind, Value};
+
+fn changes(a: &Value, b: &Value) -> Vec<String> {
+    a.diff(b).iter().map(Change::to_string).collect()
+}
+
+#[test]
+fn equal() {
+    let val = Value::Object([(0, Value::Int(1)), (1, Value::List(vec![Value::Zero]))].into());
+    assert_eq!(val.diff(&val), []);
+    assert_eq!(Value::Int(1).diff(&Value::Int(1)), []);
+}
+
+#[test]
+fn root() {
+    let (a, b) = (Value::Int(1), Value::from("a"));
+    assert_eq!(
+        a.diff(&b),
+        [Change {
+            path: String::new(),
+            kind: ChangeKind::Changed(&a, &b),
+        }]
+    );
+    assert_eq!(changes(&a, &b), ["- .: 1\n+ .: \"a\""]);
+}
+
+#[test]
+fn object() {
+    let a = Value::Object([(0, Value::Int(1)), (1, Value::Int(2)), (3, Value::Zero)].into());
+    let b = Value::Object([(0, Value::Int(1)), (2, Value::Int(2)), (3, Value::Int(4))].into());
+    assert_eq!(
+        a.diff(&b),
+        [
+            Change {
+                path: ".1".to_owned(),
+                kind: ChangeKind::Removed(&Value::Int(2)),
+            },
+            Change {
+                path: ".2".to_owned(),
+                kind: ChangeKind::Added(&Value::Int(2)),
+            },
+            Change {
+                path: ".3".to_owned(),
+                kind: ChangeKind::Changed(&Value::Zero, &Value::Int(4)),
+            },
+        ]
+    );
+}
+
+#[test]
+fn list() {
+    let a = Value::List(vec![Value::Int(1), Value::Int(2)]);
+    let b = Value::List(vec![Value::Int(1), Value::Int(3), Value::Int(4)]);
+    assert_eq!(changes(&a, &b), ["- [1]: 2\n+ [1]: 3", "+ [2]: 4"]);
+    assert_eq!(changes(&b, &a), ["- [1]: 3\n+ [1]: 2", "- [2]: 4"]);
+}
+
+#[test]
+fn map() {
+    let a = Value::Map(Map::from([
+        (Value::Int(1), Value::Int(1)),
+        (Value::from("a b"), Value::Int(1)),
+        (Value::Double(1.5), Value::Int(1)),
+    ]));
+    let b = Value::Map(Map::from([
+        (Value::Int(1), Value::Int(2)),
+        (Value::from("key"), Value::Int(1)),
+        (Value::Double(1.5), Value::Int(1)),
+    ]));
+    assert_eq!(
+        changes(&a, &b),
+        ["- [1]: 1\n+ [1]: 2", "- .\"a b\": 1", "+ .key: 1"]
+    );
+}
+
+#[test]
+fn nested() {
+    let a = Value::Object(
+        [(
+            1,
+            Value::List(vec![Value::Map(Map::from([(
+                Value::from("name"),
+                Value::from("a"),
+            )]))]),
+        )]
+        .into(),
+    );
+    let b = Value::Object(
+        [(
+            1,
+            Value::List(vec![Value::Map(Map::from([(
+                Value::from("name"),
+                Value::from("b"),
+            )]))]),
+        )]
+        .into(),
+    );
+    assert_eq!(
+        changes(&a, &b),
+        ["- .1[0].name: \"a\"\n+ .1[0].name: \"b\""]
+    );
+
+    // containers of different kinds are changed as a whole
+    let c = Value::Object([(1, Value::List(vec![]))].into());
+    let d = Value::Object([(1, Value::Map(Map::new()))].into());
+    assert_eq!(changes(&c, &d), ["- .1: []\n+ .1: map{}"]);
+}
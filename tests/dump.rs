@@ -0,0 +1,95 @@
+use hex_literal::hex;
+use serde_jce::dump::{dump, Dumper};
+
+#[test]
+fn scalar() {
+    let bytes = hex!(
+        "00 12 11 1234 22 12345678 33 0123456789abcdef"
+        "44 3fc00000 55 3ff8000000000000 6c f0 ff 00"
+    );
+    assert_eq!(
+        dump(&bytes),
+        concat!(
+            "     0  0: I8 = 18\n",
+            "     2  1: I16 = 4660\n",
+            "     5  2: I32 = 305419896\n",
+            "    10  3: I64 = 81985529216486895\n",
+            "    19  4: F32 = 1.5\n",
+            "    24  5: F64 = 1.5\n",
+            "    33  6: Zero\n",
+            "    34  255: I8 = 0\n",
+        )
+    );
+}
+
+#[test]
+fn string_and_bytes() {
+    let bytes = hex!("06 02 6162 17 00000001 63 26 02 fffe 3d 00 0003 123456");
+    assert_eq!(
+        dump(&bytes),
+        concat!(
+            "     0  0: String1[2] = \"ab\"\n",
+            "     4  1: String4[1] = \"c\"\n",
+            "    10  2: String1[2] = ff fe (not UTF-8)\n",
+            "    14  3: Bytes[3] = 12 34 56\n",
+        )
+    );
+}
+
+#[test]
+fn container() {
+    let bytes = hex!("08 0001 06 01 6b 1a 0c 0b 19 0002 00 01 00 02");
+    let expected = concat!(
+        "     0  0: Map[1]\n",
+        "     3    0: String1[1] = \"k\"\n",
+        "     6    1: StructBegin\n",
+        "     7      0: Zero\n",
+        "     8    StructEnd\n",
+        "     9  1: List[2]\n",
+        "    12    0: I8 = 1\n",
+        "    14    0: I8 = 2\n",
+    );
+    assert_eq!(dump(&bytes), expected);
+    assert_eq!(Dumper::new().max_depth(2).dump(&bytes), expected);
+    assert_eq!(
+        Dumper::new().max_depth(1).dump(&bytes),
+        concat!(
+            "     0  0: Map[1]\n",
+            "     3    0: String1[1] = \"k\"\n",
+            "     6    1: StructBegin\n",
+            "     7      ... 1 bytes\n",
+            "     8    StructEnd\n",
+            "     9  1: List[2]\n",
+            "    12    0: I8 = 1\n",
+            "    14    0: I8 = 2\n",
+        )
+    );
+    assert_eq!(
+        Dumper::new().max_depth(0).dump(&bytes),
+        concat!(
+            "     0  0: Map[1]\n",
+            "     3    ... 6 bytes\n",
+            "     9  1: List[2]\n",
+            "    12    ... 4 bytes\n",
+        )
+    );
+}
+
+#[test]
+fn malformed() {
+    assert_eq!(
+        dump(&hex!("0a 00 12 0b 0b")),
+        concat!(
+            "     0  0: StructBegin\n",
+            "     1    0: I8 = 18\n",
+            "     3  StructEnd\n",
+            "error: expected type I8 or I16 or I32 or I64 or F32 or F64 or String1 or String4 ",
+            "or Map or List or StructBegin or Zero or Bytes, found StructEnd, offset 4\n",
+        )
+    );
+    assert_eq!(dump(&hex!("0e")), "error: UnknownJceType, offset 0\n");
+    assert_eq!(
+        Dumper::new().max_depth(0).dump(&hex!("09 0002 00 01 01")),
+        concat!("     0  0: List[2]\n", "error: NotEnoughtBytes, offset 5\n")
+    );
+}
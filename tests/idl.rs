@@ -0,0 +1,188 @@
+use serde_jce::idl::{compile, generate, parse, Field, Item, Literal, Type};
+use serde_jce::{Error, ErrorKind};
+
+const HELLO_JCE: &str = include_str!("idl/hello.jce");
+const HELLO_RS: &str = include_str!("idl/hello.rs");
+
+#[test]
+fn parse_file() {
+    let idl = parse(HELLO_JCE).unwrap();
+    assert_eq!(idl.includes, ["base.jce"]);
+    assert_eq!(idl.modules.len(), 1);
+
+    let module = &idl.modules[0];
+    assert_eq!(module.name, "TestApp");
+    assert_eq!(module.items.len(), 7);
+
+    match &module.items[2] {
+        Item::Enum(e) => {
+            let values: Vec<_> = e
+                .variants
+                .iter()
+                .map(|v| (v.name.as_str(), v.value))
+                .collect();
+            assert_eq!(
+                values,
+                [("RED", 0), ("GREEN", 5), ("BLUE", 6), ("DARK_BLUE", -1)]
+            );
+        }
+        item => panic!("unexpected {:?}", item),
+    }
+
+    match &module.items[3] {
+        Item::Struct(s) => {
+            assert_eq!(s.name, "Hello");
+            assert_eq!(s.fields.len(), 10);
+            assert_eq!(
+                s.fields[2],
+                Field {
+                    tag: 2,
+                    required: false,
+                    ty: Type::Custom(vec!["Color".to_owned()]),
+                    name: "eColor".to_owned(),
+                    default: Some(Literal::Ident(vec!["GREEN".to_owned()])),
+                }
+            );
+            assert_eq!(
+                s.fields[6].ty,
+                Type::Map(
+                    Box::new(Type::String),
+                    Box::new(Type::Vector(Box::new(Type::Byte)))
+                )
+            );
+        }
+        item => panic!("unexpected {:?}", item),
+    }
+
+    match &module.items[6] {
+        Item::Interface(i) => {
+            assert_eq!(i.name, "HelloObj");
+            assert_eq!(i.operations[0].ret, Some(Type::Int));
+            assert!(i.operations[0].params[1].out);
+            assert_eq!(i.operations[1].ret, None);
+        }
+        item => panic!("unexpected {:?}", item),
+    }
+}
+
+#[test]
+fn generate_file() {
+    assert_eq!(generate(&parse(HELLO_JCE).unwrap()), HELLO_RS);
+}
+
+#[test]
+fn compile_files() {
+    let dir = std::env::temp_dir().join(format!("serde_jce_idl_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let first = dir.join("first.jce");
+    let second = dir.join("second.jce");
+    let output = dir.join("out.rs");
+    std::fs::write(&first, "module A { const int X = 1; };").unwrap();
+    std::fs::write(&second, "module A { const int Y = 2; }; module B {};").unwrap();
+
+    compile(&[&first, &second], &output).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&output).unwrap(),
+        "// Generated by serde_jce::idl, do not edit\n".to_owned()
+            + "\npub mod a {\n    pub const X: i32 = 1;\n\n    pub const Y: i32 = 2;\n}\n"
+            + "\npub mod b {\n}\n"
+    );
+
+    std::fs::write(
+        &second,
+        "module A {\n  struct S { 0 optional int a; 0 optional int b; };\n};",
+    )
+    .unwrap();
+    match compile(&[&first, &second], &output).map_err(Error::into_kind) {
+        Err(ErrorKind::Idl {
+            line: 2,
+            column: 32,
+            message,
+        }) => assert!(message.ends_with("second.jce: duplicate tag 0, already used by `a`")),
+        res => panic!("unexpected {:?}", res),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn number() {
+    let idl = parse(
+        "module A {
+            const int A = 0xff;
+            const int B = 0x1F;
+            const int C = 0XAB;
+            const long D = 0xFFL;
+            const int E = 12L;
+            const float F = 1.5f;
+            const double G = 1E+2;
+        };",
+    )
+    .unwrap();
+    let values: Vec<_> = idl.modules[0]
+        .items
+        .iter()
+        .map(|item| match item {
+            Item::Const(c) => c.value.clone(),
+            item => panic!("unexpected {:?}", item),
+        })
+        .collect();
+    assert_eq!(
+        values,
+        [
+            Literal::Int(0xff),
+            Literal::Int(0x1f),
+            Literal::Int(0xab),
+            Literal::Int(0xff),
+            Literal::Int(12),
+            Literal::Float(1.5),
+            Literal::Float(100.0),
+        ]
+    );
+}
+
+#[test]
+fn syntax_error() {
+    let err = |src| match parse(src).map_err(Error::into_kind) {
+        Err(ErrorKind::Idl {
+            line,
+            column,
+            message,
+        }) => (line, column, message),
+        res => panic!("unexpected {:?}", res),
+    };
+    assert_eq!(
+        err("module A {\n  struct S { 0 int a; };\n};"),
+        (
+            2,
+            16,
+            "expected `require` or `optional`, found `int`".to_owned()
+        )
+    );
+    assert_eq!(
+        err("module A { struct S { 256 optional int a; }; };"),
+        (1, 23, "tag must be in 0..=255".to_owned())
+    );
+    assert_eq!(
+        err("module A { struct S { 0 optional vector<int a; }; };"),
+        (1, 45, "expected `>`, found `a`".to_owned())
+    );
+    // no exponent in hex, `+` is not a token
+    assert_eq!(
+        err("module A { const int A = 0X1E+1; };"),
+        (1, 30, "unexpected character `+`".to_owned())
+    );
+    assert_eq!(
+        err("module A { /* unterminated"),
+        (1, 12, "unterminated comment".to_owned())
+    );
+    assert_eq!(
+        err("module A {"),
+        (
+            1,
+            11,
+            "expected `struct`, `enum`, `const`, `key` or `interface`, found end of file"
+                .to_owned()
+        )
+    );
+}
@@ -0,0 +1,61 @@
+// Generated by serde_jce::idl, do not edit
+
+pub mod test_app {
+    pub const MAX_LEN: i32 = 256;
+
+    pub const GREETING: &str = "hello\n";
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::serde_jce::Jce)]
+    pub enum Color {
+        Red = 0,
+        Green = 5,
+        Blue = 6,
+        DarkBlue = -1,
+    }
+
+    #[derive(Clone, Debug, PartialEq, ::serde_jce::Jce)]
+    pub struct Hello {
+        #[jce(tag = 0, required)]
+        pub s_name: ::std::string::String,
+        #[jce(tag = 1)]
+        #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+        pub i_age: ::core::option::Option<i32>,
+        #[jce(tag = 2, default = Color::Green)]
+        pub e_color: Color,
+        #[jce(tag = 3, default = 1.0)]
+        pub f_score: f32,
+        #[jce(tag = 4, required)]
+        #[serde(with = "::serde_jce::__private::serde_bytes")]
+        pub v_data: ::std::vec::Vec<u8>,
+        #[jce(tag = 5)]
+        #[serde(with = "::serde_jce::__private::serde_bytes")]
+        #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+        pub v_extra: ::core::option::Option<::std::vec::Vec<u8>>,
+        #[jce(tag = 6)]
+        #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+        pub m_files: ::core::option::Option<::std::collections::BTreeMap<::std::string::String, ::serde_jce::__private::serde_bytes::ByteBuf>>,
+        #[jce(tag = 7, default = true)]
+        pub b_ok: bool,
+        #[jce(tag = 8, default = 7)]
+        pub u_id: u32,
+        #[jce(tag = 9, default = "t")]
+        pub r#type: ::std::string::String,
+    }
+
+    impl Hello {
+        pub fn key(&self) -> (&::std::string::String, &::core::option::Option<i32>) {
+            (&self.s_name, &self.i_age)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, ::serde_jce::Jce)]
+    pub struct World {
+        #[jce(tag = 0, required)]
+        pub st_hello: Hello,
+        #[jce(tag = 1)]
+        #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+        pub v_hello: ::core::option::Option<::std::vec::Vec<Hello>>,
+        #[jce(tag = 2, default = -12)]
+        pub l_time: i64,
+    }
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "derive")]
+
+use hex_literal::hex;
+use serde_jce::{from_bytes, to_bytes};
+
+include!("idl/hello.rs");
+
+use test_app::{Color, Hello, World};
+
+fn hello() -> Hello {
+    Hello {
+        s_name: "a".to_owned(),
+        i_age: None,
+        e_color: Color::Green,
+        f_score: 1.0,
+        v_data: vec![1, 2],
+        v_extra: None,
+        m_files: None,
+        b_ok: true,
+        u_id: 7,
+        r#type: "t".to_owned(),
+    }
+}
+
+#[test]
+fn generated_default() {
+    let bytes = hex!("0a 06 01 61 4d 00 00 02 0102 0b");
+    assert_eq!(from_bytes(&bytes), Ok(hello()));
+
+    let bytes = hex!("0a 4d 00 00 02 0102 0b");
+    assert_eq!(
+        from_bytes::<Hello>(&bytes).map_err(serde_jce::Error::into_kind),
+        Err(serde_jce::ErrorKind::MissingField {
+            tag: 0,
+            name: "Hello"
+        })
+    );
+}
+
+#[test]
+fn generated_round_trip() {
+    let world = World {
+        st_hello: Hello {
+            i_age: Some(18),
+            e_color: Color::DarkBlue,
+            v_extra: Some(vec![3]),
+            ..hello()
+        },
+        v_hello: Some(vec![hello()]),
+        l_time: 0,
+    };
+    let bytes = to_bytes(&world).unwrap();
+    assert_eq!(from_bytes(&bytes), Ok(world.clone()));
+    assert_eq!(world.st_hello.key(), (&"a".to_owned(), &Some(18)));
+    assert_eq!(test_app::MAX_LEN, 256);
+}
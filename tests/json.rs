@@ -0,0 +1,130 @@
+#![cfg(feature = "json")]
+
+use serde_jce::{ErrorKind, JsonHint, Value};
+use serde_json::json;
+
+fn round_trip(val: Value, json: serde_json::Value, hint: JsonHint) {
+    assert_eq!(val.to_json(), json);
+    assert_eq!(Value::from_json(&json, &hint), Ok(val));
+}
+
+#[test]
+fn scalar() {
+    round_trip(Value::Zero, json!(null), JsonHint::Any);
+    round_trip(Value::Int(-0x12), json!(-0x12), JsonHint::Any);
+    round_trip(Value::Double(1.5), json!(1.5), JsonHint::Any);
+    round_trip(Value::Double(1.0), json!(1.0), JsonHint::Double);
+    round_trip(Value::Float(1.1), json!(1.1f32 as f64), JsonHint::Float);
+    round_trip("ab".into(), json!("ab"), JsonHint::Any);
+    round_trip(
+        Value::Bytes(vec![0x12, 0x34]),
+        json!("EjQ="),
+        JsonHint::Bytes,
+    );
+}
+
+#[test]
+fn non_finite() {
+    round_trip(
+        Value::Double(f64::INFINITY),
+        json!("Infinity"),
+        JsonHint::Double,
+    );
+    round_trip(
+        Value::Float(f32::NEG_INFINITY),
+        json!("-Infinity"),
+        JsonHint::Float,
+    );
+    round_trip(Value::Double(f64::NAN), json!("NaN"), JsonHint::Double);
+    // a string without hint
+    assert_eq!(
+        Value::from_json(&json!("NaN"), &JsonHint::Any),
+        Ok("NaN".into())
+    );
+}
+
+#[test]
+fn list() {
+    round_trip(
+        Value::List(vec![Value::Int(1), Value::Zero, "a".into()]),
+        json!([1, null, "a"]),
+        JsonHint::Any,
+    );
+    round_trip(
+        Value::List(vec![Value::Float(1.0), Value::Float(2.0)]),
+        json!([1.0, 2.0]),
+        JsonHint::List(Box::new(JsonHint::Float)),
+    );
+    round_trip(
+        Value::List(vec![Value::Bytes(vec![0x12]), Value::Int(1)]),
+        json!(["Eg==", 1]),
+        JsonHint::Tuple(vec![JsonHint::Bytes]),
+    );
+}
+
+#[test]
+fn map() {
+    round_trip(
+        Value::Map([("a".into(), Value::Int(1)), ("b".into(), Value::Zero)].into()),
+        json!({"a": 1, "b": null}),
+        JsonHint::Any,
+    );
+    round_trip(
+        Value::Map([(Value::Int(1), "a".into()), (Value::Int(2), "b".into())].into()),
+        json!([[1, "a"], [2, "b"]]),
+        JsonHint::Map(Box::new(JsonHint::Int), Box::new(JsonHint::String)),
+    );
+    round_trip(
+        Value::Map([].into()),
+        json!({}),
+        JsonHint::Map(Box::new(JsonHint::Any), Box::new(JsonHint::Any)),
+    );
+
+    // stringified keys
+    assert_eq!(
+        Value::from_json(
+            &json!({"1": "a", "2": "b"}),
+            &JsonHint::Map(Box::new(JsonHint::Int), Box::new(JsonHint::String))
+        ),
+        Ok(Value::Map(
+            [(Value::Int(1), "a".into()), (Value::Int(2), "b".into())].into()
+        ))
+    );
+}
+
+#[test]
+fn object() {
+    round_trip(
+        Value::Object(
+            [
+                (0, Value::Int(1)),
+                (1, Value::Object([(0, Value::Float(0.5))].into())),
+                (255, Value::List(vec![])),
+            ]
+            .into(),
+        ),
+        json!({"0": 1, "1": {"0": 0.5}, "255": []}),
+        JsonHint::Object([(1, JsonHint::Object([(0, JsonHint::Float)].into()))].into()),
+    );
+    round_trip(Value::Object([].into()), json!({}), JsonHint::Any);
+}
+
+#[test]
+fn error() {
+    let hint = JsonHint::Object([(1, JsonHint::List(Box::new(JsonHint::Int)))].into());
+    let err = Value::from_json(&json!({"1": [1, "a"]}), &hint).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::Json("expected an integer, found \"a\"".to_owned())
+    );
+    assert_eq!(err.path(), ".1[1]");
+
+    let err = Value::from_json(
+        &json!({"key": "!"}),
+        &JsonHint::Map(Box::new(JsonHint::String), Box::new(JsonHint::Bytes)),
+    )
+    .unwrap_err();
+    assert_eq!(err.path(), ".key");
+
+    assert!(Value::from_json(&json!({"256": 1}), &JsonHint::Object([].into())).is_err());
+}
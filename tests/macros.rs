@@ -0,0 +1,163 @@
+use std::collections::BTreeMap as Map;
+
+use serde_jce::{jce, jce_typed, TypedValue, Value};
+
+#[test]
+fn scalar() {
+    assert_eq!(jce!(zero), Value::Zero);
+    assert_eq!(jce!(1), Value::Int(1));
+    assert_eq!(jce!(-1i8), Value::Int(-1));
+    assert_eq!(jce!(u32::MAX), Value::Int(u32::MAX as i64));
+    assert_eq!(jce!(true), Value::Int(1));
+    assert_eq!(jce!(2.0f32), Value::Float(2.0));
+    assert_eq!(jce!(2.0), Value::Double(2.0));
+    assert_eq!(jce!("abc"), Value::from("abc"));
+    assert_eq!(jce!(bytes[]), Value::Bytes(vec![]));
+    assert_eq!(jce!(bytes[1, 2,]), Value::Bytes(vec![1, 2]));
+
+    let bytes = vec![1u8, 2];
+    assert_eq!(jce!(bytes), Value::Bytes(vec![1, 2]));
+}
+
+#[test]
+fn list() {
+    assert_eq!(jce!([]), Value::List(vec![]));
+    assert_eq!(
+        jce!([1, 1 + 1, -3, [zero], bytes[1],]),
+        Value::List(vec![
+            Value::Int(1),
+            Value::Int(2),
+            Value::Int(-3),
+            Value::List(vec![Value::Zero]),
+            Value::Bytes(vec![1]),
+        ])
+    );
+}
+
+#[test]
+fn object() {
+    let mut obj = Map::new();
+    obj.insert(0, Value::Int(1));
+    obj.insert(1, Value::from("abc"));
+    obj.insert(
+        2,
+        Value::List(vec![Value::Int(1), Value::Int(2), Value::Int(3)]),
+    );
+    assert_eq!(jce!({ 0: 1, 1: "abc", 2: [1, 2, 3] }), Value::Object(obj));
+    assert_eq!(jce!({}), Value::Object(Map::new()));
+}
+
+#[test]
+fn map() {
+    let mut map = Map::new();
+    map.insert(Value::from("k"), Value::Bytes(vec![1, 2]));
+    map.insert(Value::Int(-1), Value::Zero);
+    assert_eq!(jce!({ "k": bytes[1, 2], -1: zero }), Value::Map(map));
+
+    let mut map = Map::new();
+    map.insert(Value::Int(0), Value::Double(1.5));
+    assert_eq!(jce!(map { 0: 1.5 }), Value::Map(map));
+    assert_eq!(jce!({ 256: 1.5 }).map().map(|m| m.len()), Some(1));
+    assert_eq!(jce!(map {}), Value::Map(Map::new()));
+}
+
+#[test]
+fn nested() {
+    let val = jce!({
+        0: 1,
+        1: "abc",
+        2: [1, 2, 3],
+        3: { "k": bytes[1, 2] },
+    });
+    assert_eq!(
+        val.to_text(),
+        r#"{0: 1, 1: "abc", 2: [1, 2, 3], 3: map{"k": bytes"AQI="}}"#
+    );
+    assert_eq!(
+        serde_jce::to_bytes(&jce!({ 0: 0x12, 1: "ab" })),
+        Ok(vec![0x0a, 0x00, 0x12, 0x16, 0x02, 0x61, 0x62, 0x0b])
+    );
+}
+
+#[test]
+fn from() {
+    assert_eq!(Value::from(vec![1u8]), Value::Bytes(vec![1]));
+    assert_eq!(
+        Value::from(vec![Value::Zero]),
+        Value::List(vec![Value::Zero])
+    );
+    assert_eq!(
+        Value::from(Map::<u8, Value>::new()),
+        Value::Object(Map::new())
+    );
+    assert_eq!(
+        Value::from(Map::<Value, Value>::new()),
+        Value::Map(Map::new())
+    );
+    assert_eq!(Value::try_from(u64::MAX / 2), Ok(Value::Int(i64::MAX)));
+    assert!(Value::try_from(u64::MAX).is_err());
+    assert_eq!(Value::try_from(3usize), Ok(Value::Int(3)));
+}
+
+#[test]
+fn long() {
+    let val = jce!([
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47,
+        48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70,
+        71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93,
+        94, 95, 96, 97, 98, 99,
+    ]);
+    assert_eq!(val, Value::List((0..100).map(Value::Int).collect()));
+
+    let val = jce!({
+        0: "a", 1: "b", 2: "c", 3: "d", 4: "e", 5: "f", 6: "g", 7: "h", 8: "i", 9: "j",
+        10: "a", 11: "b", 12: "c", 13: "d", 14: "e", 15: "f", 16: "g", 17: "h", 18: "i", 19: "j",
+        20: "a", 21: "b", 22: "c", 23: "d", 24: "e", 25: "f", 26: "g", 27: "h", 28: "i", 29: "j",
+        30: "a", 31: "b", 32: "c", 33: "d", 34: "e", 35: "f", 36: "g", 37: "h", 38: "i", 39: "j",
+    });
+    assert_eq!(val.obj().map(|obj| obj.len()), Some(40));
+}
+
+#[test]
+fn typed() {
+    assert_eq!(jce_typed!(zero), TypedValue::Zero);
+    assert_eq!(jce_typed!(1i8), TypedValue::I8(1));
+    assert_eq!(jce_typed!(1i16), TypedValue::I16(1));
+    assert_eq!(jce_typed!(1), TypedValue::I32(1));
+    assert_eq!(jce_typed!(-1i64), TypedValue::I64(-1));
+    assert_eq!(jce_typed!(2.0f32), TypedValue::F32(2.0));
+    assert_eq!(jce_typed!(2.0), TypedValue::F64(2.0));
+    assert_eq!(jce_typed!("ab"), TypedValue::String1("ab".to_owned()));
+    assert_eq!(
+        jce_typed!("a".repeat(256)),
+        TypedValue::String4("a".repeat(256))
+    );
+    assert_eq!(jce_typed!(bytes[1, 2]), TypedValue::Bytes(vec![1, 2]));
+    assert_eq!(
+        jce_typed!([1i8, [zero]]),
+        TypedValue::List(vec![
+            TypedValue::I8(1),
+            TypedValue::List(vec![TypedValue::Zero])
+        ])
+    );
+    assert_eq!(
+        jce_typed!({ 2: 1i8, 0: map{ "k": 3i64 } }),
+        TypedValue::Struct(vec![
+            (2, TypedValue::I8(1)),
+            (
+                0,
+                TypedValue::Map(vec![(
+                    TypedValue::String1("k".to_owned()),
+                    TypedValue::I64(3)
+                )])
+            ),
+        ])
+    );
+    assert_eq!(
+        serde_jce::to_bytes(&jce_typed!({ 0: 1i64, 1: 2.0f32 })),
+        Ok(vec![
+            0x0a, 0x03, 0, 0, 0, 0, 0, 0, 0, 1, 0x14, 0x40, 0, 0, 0, 0x0b
+        ])
+    );
+}
@@ -1,5 +1,5 @@
 use hex_literal::hex;
-use serde_jce::{JceParser, JceType};
+use serde_jce::{Error, ErrorKind, JceParser, JceType};
 
 macro_rules! parser_test {
     ($func:ident, $value:expr, $expected:expr) => {
@@ -210,7 +210,7 @@ fn ignore_bytes() {
 fn extra_input() {
     let mut parser = JceParser::from_bytes(&hex!("00 12 34"));
     assert_eq!(parser.i8(), Ok(0x12));
-    assert_eq!(parser.done(), false);
+    assert!(!parser.done());
 }
 
 #[test]
@@ -234,3 +234,195 @@ fn wrong_parse() {
     assert_eq!(parser.pick_type(), Ok(JceType::I64));
     assert!(parser.i16().is_err());
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// reader test
+
+#[test]
+fn reader() {
+    let bytes = hex!(
+        "08 0001"
+        "06 05 6669727374"
+        "1d 00 0004 12345678"
+        "fc ab"
+    );
+    let mut parser = JceParser::from_reader(&bytes[..]);
+    assert_eq!(parser.map(), Ok(1));
+    assert_eq!(parser.str(), Ok("first".to_owned()));
+    assert_eq!(parser.pick_head(), Ok((1, JceType::Bytes)));
+    assert_eq!(parser.bytes(), Ok(hex!("12345678").to_vec()));
+    assert_eq!(parser.done(), Ok(false));
+    assert_eq!(parser.pick_head(), Ok((0xab, JceType::Zero)));
+    assert_eq!(parser.zero(), Ok(()));
+    assert_eq!(parser.done(), Ok(true));
+}
+
+#[test]
+fn reader_ignore() {
+    let bytes = hex!("0a 0012 16 05 6669727374 0b" "10 12");
+    let mut parser = JceParser::from_reader(&bytes[..]);
+    assert_eq!(parser.ignore(), Ok(()));
+    assert_eq!(parser.i8(), Ok(0x12));
+    assert_eq!(parser.done(), Ok(true));
+}
+
+#[test]
+fn reader_less_input() {
+    let mut parser = JceParser::from_reader(&hex!("03 12 34")[..]);
+    assert_eq!(
+        parser.i64().map_err(Error::into_kind),
+        Err(ErrorKind::UnexpectedEof)
+    );
+
+    let mut parser = JceParser::from_reader(&hex!("06 05 6669")[..]);
+    assert_eq!(
+        parser.str().map_err(Error::into_kind),
+        Err(ErrorKind::UnexpectedEof)
+    );
+}
+
+#[test]
+fn error_offset() {
+    let mut parser = JceParser::from_bytes(&hex!("00 12 16 01 61"));
+    assert_eq!(parser.i8(), Ok(0x12));
+    let err = parser.i32().unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::WrongType {
+            expected: &[JceType::Zero, JceType::I8, JceType::I16, JceType::I32],
+            actual: JceType::String1,
+        }
+    );
+    assert_eq!(err.offset(), Some(2));
+
+    let mut parser = JceParser::from_reader(&hex!("00 12 06 05 6669")[..]);
+    assert_eq!(parser.i8(), Ok(0x12));
+    let err = parser.str().unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnexpectedEof);
+    assert_eq!(err.offset(), Some(2));
+}
+
+#[test]
+fn atomic() {
+    let bytes = hex!("0d 00 00 02 0102 16 02 61 ff 2a 00 01 0c");
+    let mut parser = JceParser::from_bytes(&bytes);
+    assert!(parser.i32().is_err());
+    assert!(parser.str().is_err());
+    assert!(parser.list().is_err());
+    assert_eq!(parser.position(), 0);
+    assert_eq!(parser.bytes(), Ok(&hex!("0102")[..]));
+
+    // not utf-8
+    assert_eq!(
+        parser.str().map_err(Error::into_kind),
+        Err(ErrorKind::StringIsNotUtf8)
+    );
+    assert_eq!(parser.position(), 6);
+    assert_eq!(parser.ignore(), Ok(()));
+
+    // struct without end
+    assert!(parser.ignore().is_err());
+    assert_eq!(parser.position(), 10);
+    assert_eq!(parser.struct_begin(), Ok(()));
+    assert_eq!(parser.i8(), Ok(1));
+}
+
+#[test]
+fn reader_atomic() {
+    let bytes = hex!("0d 00 00 02 0102 2a 00 01 0c");
+    let mut parser = JceParser::from_reader(&bytes[..]);
+    assert!(parser.str().is_err());
+    assert_eq!(parser.position(), 0);
+    assert_eq!(parser.bytes(), Ok(hex!("0102").to_vec()));
+
+    assert_eq!(
+        parser.ignore().map_err(Error::into_kind),
+        Err(ErrorKind::UnexpectedEof)
+    );
+    assert_eq!(parser.position(), 6);
+    assert_eq!(parser.struct_begin(), Ok(()));
+    assert_eq!(parser.i8(), Ok(1));
+    assert_eq!(parser.zero(), Ok(()));
+    assert_eq!(parser.done(), Ok(true));
+}
+
+#[test]
+fn checkpoint() {
+    let mut parser = JceParser::from_bytes(&hex!("0a 00 12 11 3456 0b"));
+    let checkpoint = parser.checkpoint();
+    assert_eq!(parser.ignore(), Ok(()));
+    assert!(parser.done());
+
+    assert_eq!(parser.rewind(checkpoint), Ok(()));
+    assert_eq!(parser.position(), 0);
+    assert_eq!(parser.struct_begin(), Ok(()));
+    assert_eq!(parser.i8(), Ok(0x12));
+
+    // taken from a longer parser
+    let mut short = JceParser::from_bytes(&hex!("00 12"));
+    let res = short.rewind(parser.checkpoint());
+    assert_eq!(
+        res.map_err(Error::into_kind),
+        Err(ErrorKind::InvalidCheckpoint)
+    );
+    assert_eq!(short.position(), 0);
+    assert!(!short.done());
+}
+
+#[test]
+fn seek_tag() {
+    // `{ 0: 0x12, 1: { 0: "a" }, 2: [0x34] }`
+    let bytes = hex!("0a 00 12 1a 06 01 61 0b 29 0001 00 34 0b");
+    let mut parser = JceParser::from_bytes(&bytes);
+    assert_eq!(parser.struct_begin(), Ok(()));
+    assert_eq!(parser.seek_tag(2), Ok(Some(JceType::List)));
+    assert_eq!(parser.position(), 8);
+    assert_eq!(parser.seek_tag(2), Ok(Some(JceType::List)));
+    assert_eq!(parser.read_field::<Vec<i8>>(2), Ok(Some(vec![0x34])));
+    assert_eq!(parser.seek_tag(0), Ok(None));
+    assert_eq!(parser.struct_end(), Ok(()));
+
+    // bare fields end at the end of input
+    let mut parser = JceParser::from_bytes(&hex!("00 12 11 3456"));
+    assert_eq!(parser.read_field::<i16>(1), Ok(Some(0x3456)));
+    assert_eq!(parser.read_field::<i16>(2), Ok(None));
+    assert!(parser.done());
+}
+
+#[test]
+fn reader_seek_tag() {
+    let bytes = hex!("0a 00 12 1a 06 01 61 0b 29 0001 00 34 0b");
+    let mut parser = JceParser::from_reader(&bytes[..]);
+    assert_eq!(parser.struct_begin(), Ok(()));
+    assert_eq!(parser.read_field::<Vec<i8>>(2), Ok(Some(vec![0x34])));
+    assert_eq!(parser.struct_end(), Ok(()));
+    assert_eq!(parser.done(), Ok(true));
+}
+
+#[test]
+fn read_field_error() {
+    let bytes = hex!("0a 00 12 19 0002 00 01 06 01 61 0b");
+    let mut parser = JceParser::from_bytes(&bytes);
+    assert_eq!(parser.struct_begin(), Ok(()));
+    let err = parser.read_field::<Vec<i8>>(1).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::WrongType {
+            expected: &[JceType::Zero, JceType::I8],
+            actual: JceType::String1,
+        }
+    );
+    assert_eq!(err.path(), ".1[1]");
+    assert_eq!(err.offset(), Some(8));
+    assert_eq!(parser.position(), 1);
+
+    // the field is not complete
+    let mut parser = JceParser::from_reader(&bytes[..10]);
+    assert_eq!(parser.struct_begin(), Ok(()));
+    assert_eq!(
+        parser.seek_tag(3).map_err(Error::into_kind),
+        Err(ErrorKind::UnexpectedEof)
+    );
+    assert_eq!(parser.position(), 1);
+    assert_eq!(parser.i8(), Ok(0x12));
+}
@@ -0,0 +1,125 @@
+use serde_jce::{ErrorKind, Value};
+
+fn packet() -> Value {
+    Value::from_text(
+        r#"{
+            0: 1,
+            3: [map{"key": [1, 2], "a/b": zero, 7: "seven"}],
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn pointer() {
+    let val = packet();
+    assert_eq!(val.pointer(""), Ok(&val));
+    assert_eq!(val.pointer("/0"), Ok(&Value::Int(1)));
+    assert_eq!(val.pointer(r#"/3/0/"key"/1"#), Ok(&Value::Int(2)));
+    assert_eq!(val.pointer(r#"/3/0/"a/b""#), Ok(&Value::Zero));
+    assert_eq!(val.pointer("/3/0/7"), Ok(&Value::from("seven")));
+}
+
+#[test]
+fn pointer_not_found() {
+    let val = packet();
+    for (path, prefix) in [
+        ("/1", "/1"),
+        ("/256", "/256"),
+        ("/3/1", "/3/1"),
+        ("/3/x", "/3/x"),
+        (r#"/3/0/"nope"/0"#, r#"/3/0/"nope""#),
+        ("/0/0", "/0/0"),
+    ] {
+        let err = val.pointer(path).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::PathNotFound, "{}", path);
+        assert_eq!(err.path(), prefix);
+    }
+}
+
+#[test]
+fn pointer_invalid() {
+    let val = packet();
+    let err = val.pointer("3").unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidPath(_)));
+
+    let err = val.pointer(r#"/3/0/"key"#).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidPath(_)));
+
+    let err = val.pointer("/3/0/key").unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::InvalidPath("invalid key `key`".to_owned())
+    );
+    assert_eq!(err.path(), "/3/0/key");
+    assert_eq!(
+        err.to_string(),
+        "invalid path: invalid key `key` at /3/0/key"
+    );
+}
+
+#[test]
+fn pointer_mut() {
+    let mut val = packet();
+    *val.pointer_mut(r#"/3/0/"key"/0"#).unwrap() = Value::Int(5);
+    assert_eq!(
+        val.pointer(r#"/3/0/"key""#),
+        Ok(&Value::from_text("[5, 2]").unwrap())
+    );
+    assert_eq!(
+        val.pointer_mut("/3/2").unwrap_err().kind(),
+        &ErrorKind::PathNotFound
+    );
+}
+
+#[test]
+fn set_at_path() {
+    let mut val = packet();
+    assert_eq!(
+        val.set_at_path("/0", Value::Int(2)),
+        Ok(Some(Value::Int(1)))
+    );
+    assert_eq!(val.set_at_path("/1", Value::Zero), Ok(None));
+    assert_eq!(val.set_at_path("/3/1", Value::Int(3)), Ok(None));
+    assert_eq!(val.set_at_path(r#"/3/0/"new""#, Value::Int(4)), Ok(None));
+    assert_eq!(val.pointer(r#"/3/0/"new""#), Ok(&Value::Int(4)));
+    assert_eq!(val.pointer("/1"), Ok(&Value::Zero));
+    assert_eq!(val.pointer("/3/1"), Ok(&Value::Int(3)));
+
+    let err = val.set_at_path("/3/5", Value::Zero).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::PathNotFound);
+    assert_eq!(err.path(), "/3/5");
+    let err = val.set_at_path("/9/0", Value::Zero).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::PathNotFound);
+    assert_eq!(err.path(), "/9");
+    let err = val.set_at_path("/300", Value::Zero).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidPath(_)));
+    let err = val.set_at_path("/0/0", Value::Zero).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::PathNotFound);
+
+    assert_eq!(val.set_at_path("", Value::Zero).map(|_| ()), Ok(()));
+    assert_eq!(val, Value::Zero);
+}
+
+#[test]
+fn index() {
+    let mut val = packet();
+    assert_eq!(val[0], Value::Int(1));
+    assert_eq!(val[3][0]["key"][1], Value::Int(2));
+    assert_eq!(val[3][0][7], Value::from("seven"));
+
+    val[3][0]["key"][1] = Value::Int(3);
+    assert_eq!(val.pointer(r#"/3/0/"key"/1"#), Ok(&Value::Int(3)));
+}
+
+#[test]
+#[should_panic]
+fn index_missing() {
+    let _ = &packet()[3][1];
+}
+
+#[test]
+#[should_panic]
+fn index_not_container() {
+    let _ = &packet()[0]["key"];
+}
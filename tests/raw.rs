@@ -0,0 +1,124 @@
+use hex_literal::hex;
+use serde::{Deserialize, Serialize};
+use serde_jce::{Deserializer, Error, ErrorKind, JceType, RawValue};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Packet<'a> {
+    #[serde(rename = "0")]
+    id: i32,
+    #[serde(rename = "1", borrow)]
+    body: RawValue<'a>,
+    #[serde(rename = "2", borrow)]
+    extra: Vec<RawValue<'a>>,
+}
+
+/// `{ 0: 1, 1: { 0: "ab" }, 2: [0x12, 0x3456] }`
+const PACKET: [u8; 18] = hex!("0a 00 01 1a 06 02 6162 0b 29 0002 00 12 01 3456 0b");
+
+#[test]
+fn capture() {
+    let packet: Packet = serde_jce::from_bytes(&PACKET).unwrap();
+    assert_eq!(packet.id, 1);
+    assert_eq!(packet.body.get(), hex!("1a 06 02 6162 0b"));
+    assert_eq!(packet.body.tag(), 1);
+    assert_eq!(packet.body.jce_type(), JceType::StructBegin);
+    assert_eq!(packet.extra[0].get(), hex!("00 12"));
+    assert_eq!(packet.extra[1].get(), hex!("01 3456"));
+
+    // borrowed from the input
+    assert!(PACKET.as_ptr_range().contains(&packet.body.get().as_ptr()));
+
+    #[derive(PartialEq, Debug, Deserialize)]
+    struct Body {
+        #[serde(rename = "0")]
+        v0: String,
+    }
+    assert_eq!(
+        serde_jce::from_bytes(packet.body.get()),
+        Ok(Body {
+            v0: "ab".to_owned()
+        })
+    );
+    assert_eq!(serde_jce::from_bytes(packet.extra[1].get()), Ok(0x3456));
+}
+
+#[test]
+fn verbatim() {
+    let packet: Packet = serde_jce::from_bytes(&PACKET).unwrap();
+    assert_eq!(serde_jce::to_bytes(&packet), Ok(PACKET.to_vec()));
+}
+
+#[test]
+fn retag() {
+    let raw = RawValue::new(&hex!("1a 06 02 6162 0b")).unwrap();
+    assert_eq!(
+        serde_jce::to_bytes(&raw),
+        Ok(hex!("0a 06 02 6162 0b").to_vec())
+    );
+    assert_eq!(
+        serde_jce::to_bytes_with_tag(0xab, &raw),
+        Ok(hex!("fa ab 06 02 6162 0b").to_vec())
+    );
+
+    let raw = RawValue::new(&hex!("f0 ab 12")).unwrap();
+    assert_eq!(raw.tag(), 0xab);
+    assert_eq!(
+        serde_jce::to_bytes_with_tag(3, &raw),
+        Ok(hex!("30 12").to_vec())
+    );
+}
+
+#[test]
+fn invalid() {
+    let err = |bytes: &[u8]| RawValue::new(bytes).map(|_| ()).map_err(Error::into_kind);
+    assert_eq!(err(&hex!("00 12 00")), Err(ErrorKind::TrailingBytes));
+    assert_eq!(err(&hex!("01 12")), Err(ErrorKind::NotEnoughtBytes));
+    assert!(matches!(
+        err(&hex!("0b")),
+        Err(ErrorKind::WrongType {
+            actual: JceType::StructEnd,
+            ..
+        })
+    ));
+    assert_eq!(
+        RawValue::from_vec(hex!("0c").to_vec()).map(|raw| raw.jce_type()),
+        Ok(JceType::Zero)
+    );
+}
+
+#[test]
+fn reader() {
+    let mut de = Deserializer::from_reader(&PACKET[..]);
+    let packet = Packet::deserialize(&mut de).unwrap();
+    assert_eq!(de.end(), Ok(()));
+
+    let body: RawValue<'static> = packet.body.into_owned();
+    assert_eq!(body.get(), hex!("1a 06 02 6162 0b"));
+    assert_eq!(
+        packet.extra.iter().map(RawValue::get).collect::<Vec<_>>(),
+        [&hex!("00 12")[..], &hex!("01 3456")[..]]
+    );
+}
+
+#[test]
+fn invalid_deserialize() {
+    use serde::de::value::{BorrowedBytesDeserializer, BytesDeserializer, Error};
+
+    let raw = RawValue::deserialize(BytesDeserializer::<Error>::new(&[]));
+    assert!(raw.is_err());
+    let raw = RawValue::deserialize(BorrowedBytesDeserializer::<Error>::new(&hex!("0e")));
+    assert!(raw.is_err());
+    let raw = RawValue::deserialize(BorrowedBytesDeserializer::<Error>::new(&hex!("00 12")));
+    assert_eq!(raw.map(|raw| raw.jce_type()), Ok(JceType::I8));
+}
+
+#[test]
+fn invalid_serialize() {
+    #[derive(Serialize)]
+    #[serde(rename = "$serde_jce::RawValue")]
+    struct Forged<'a>(#[serde(with = "serde_bytes")] &'a [u8]);
+
+    let err = |bytes: &[u8]| serde_jce::to_bytes(&Forged(bytes)).map_err(Error::into_kind);
+    assert_eq!(err(&[]), Err(ErrorKind::NotEnoughtBytes));
+    assert_eq!(err(&hex!("f0")), Err(ErrorKind::NotEnoughtBytes));
+}
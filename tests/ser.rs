@@ -247,3 +247,105 @@ fn jce_struct() {
 
     assert_eq!(serde_jce::to_bytes(&test).unwrap(), expected);
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// writer test
+
+#[test]
+fn writer_reuse() {
+    let mut buf = Vec::new();
+    serde_jce::to_writer(&mut buf, &0x12_i8).unwrap();
+    serde_jce::to_writer_with_tag(&mut buf, 0xab, &"1234").unwrap();
+    assert_eq!(buf, hex!("00 12" "f6 ab 04 31323334"));
+}
+
+#[test]
+fn writer_serializer() {
+    let mut ser = serde_jce::Serializer::from_writer(std::io::Cursor::new(Vec::new()));
+    ser.tag = 1;
+    vec![0x12_i8, 0x34].serialize(&mut ser).unwrap();
+    assert_eq!(ser.into_inner().into_inner(), hex!("19 0002 0012 0034"));
+}
+
+#[test]
+fn writer_error() {
+    /// Accept only a few bytes, like a full disk
+    struct Full(usize);
+
+    impl std::io::Write for Full {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.0 == 0 {
+                Err(std::io::ErrorKind::WriteZero.into())
+            } else {
+                let len = std::cmp::min(self.0, buf.len());
+                self.0 -= len;
+                Ok(len)
+            }
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    assert_eq!(
+        serde_jce::to_writer(Full(4), &"1234").map_err(serde_jce::Error::into_kind),
+        Err(serde_jce::ErrorKind::Io(std::io::ErrorKind::WriteZero))
+    );
+    assert_eq!(serde_jce::to_writer(Full(6), &"1234"), Ok(()));
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// enum test
+
+#[derive(Serialize)]
+enum Enum {
+    Unit,
+    Other,
+    Newtype(i16),
+    Tuple(i8, String),
+    Struct {
+        #[serde(rename = "0")]
+        v0: i8,
+        #[serde(rename = "2")]
+        v2: i8,
+    },
+}
+
+ser_test!(enum_unit_first, hex!("0c"), Enum, Enum::Unit);
+ser_test!(enum_unit, hex!("00 01"), Enum, Enum::Other);
+ser_test!(
+    enum_newtype,
+    hex!("0a 0002 11 1234 0b"),
+    Enum,
+    Enum::Newtype(0x1234)
+);
+ser_test!(
+    enum_tuple,
+    hex!("0a 0003 19 0002 0012 06 01 61 0b"),
+    Enum,
+    Enum::Tuple(0x12, "a".to_owned())
+);
+ser_test!(
+    enum_struct,
+    hex!("0a 0004 1a 0012 2034 0b 0b"),
+    Enum,
+    Enum::Struct { v0: 0x12, v2: 0x34 }
+);
+
+#[test]
+fn enum_in_struct() {
+    #[derive(Serialize)]
+    struct Test {
+        #[serde(rename = "1")]
+        v1: Enum,
+        #[serde(rename = "2")]
+        v2: Enum,
+    }
+    let val = Test {
+        v1: Enum::Other,
+        v2: Enum::Newtype(0x12),
+    };
+    let expected = hex!("0a 1001 2a 0002 1012 0b 0b");
+    assert_eq!(serde_jce::to_bytes(&val), Ok(expected.to_vec()));
+}
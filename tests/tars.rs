@@ -0,0 +1,303 @@
+use hex_literal::hex;
+use serde::{Deserialize, Serialize};
+use serde_jce::tars::{
+    FrameReader, FrameWriter, RequestPacket, ResponsePacket, UniAttribute, TARS_VERSION,
+    TUP_SIMPLE_VERSION, TUP_VERSION,
+};
+use serde_jce::{Error, ErrorKind};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename = "$serde_jce::bare")]
+struct Args {
+    #[serde(rename = "1")]
+    v1: String,
+    #[serde(rename = "2")]
+    v2: i32,
+}
+
+#[test]
+fn request() {
+    let mut req = RequestPacket::new("A.B.C", "f");
+    req.request_id = 0x12;
+    req.timeout = 3000;
+    req.context.insert("k".to_owned(), "v".to_owned());
+    req.set_body(&Args {
+        v1: "a".to_owned(),
+        v2: 0x34,
+    })
+    .unwrap();
+    let bytes = hex!(
+        "10 01"
+        "2c"
+        "3c"
+        "40 12"
+        "56 05 412e422e43"
+        "66 01 66"
+        "7d 00 0005 16 01 61 20 34"
+        "81 0bb8"
+        "98 0001 06 01 6b 16 01 76"
+        "a8 0c"
+    );
+    assert_eq!(serde_jce::to_bytes(&req), Ok(bytes.to_vec()));
+    assert_eq!(serde_jce::from_bytes(&bytes), Ok(req.clone()));
+    assert_eq!(req.version, TARS_VERSION);
+    assert_eq!(
+        req.body::<Args>(),
+        Ok(Args {
+            v1: "a".to_owned(),
+            v2: 0x34
+        })
+    );
+}
+
+#[test]
+fn response() {
+    let mut req = RequestPacket::new("A.B.C", "f");
+    req.request_id = 0x12;
+    let mut rsp = ResponsePacket::reply_to(&req);
+    rsp.set_body(&"ok").unwrap();
+    let bytes = hex!(
+        "10 01"
+        "2c"
+        "30 12"
+        "4c"
+        "5c"
+        "6d 00 0004 06 02 6f6b"
+        "78 0c"
+        "86 00"
+        "98 0c"
+    );
+    assert_eq!(serde_jce::to_bytes(&rsp), Ok(bytes.to_vec()));
+    assert_eq!(serde_jce::from_bytes(&bytes), Ok(rsp.clone()));
+    assert_eq!(rsp.body::<&str>(), Ok("ok"));
+}
+
+#[test]
+fn response_without_optional() {
+    let bytes = hex!(
+        "10 01"
+        "2c"
+        "30 12"
+        "4c"
+        "50 ff"
+        "6c"
+        "78 0c"
+    );
+    let rsp: ResponsePacket = serde_jce::from_bytes(&bytes).unwrap();
+    assert_eq!(rsp.request_id, 0x12);
+    assert_eq!(rsp.ret, -1);
+    assert_eq!(rsp.result_desc, "");
+}
+
+#[test]
+fn wrapped_packet() {
+    // bare struct must not start with `StructBegin`
+    let bytes = hex!("0a 10 01 0b");
+    assert!(serde_jce::from_bytes::<RequestPacket>(&bytes).is_err());
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// uni attribute test
+
+#[test]
+fn uni_attribute_simple() {
+    let mut attr = UniAttribute::new(TUP_SIMPLE_VERSION).unwrap();
+    attr.put("a", &0x12_i8).unwrap();
+    attr.put("b", &"c").unwrap();
+    let bytes = hex!(
+        "08 0002"
+        "06 01 61 1d 00 0002 00 12"
+        "06 01 62 1d 00 0003 06 01 63"
+    );
+    assert_eq!(attr.encode(), Ok(bytes.to_vec()));
+
+    let attr = UniAttribute::decode(TUP_SIMPLE_VERSION, &bytes).unwrap();
+    assert_eq!(attr.get::<i8>("a"), Ok(Some(0x12)));
+    assert_eq!(attr.get::<&str>("b"), Ok(Some("c")));
+    assert_eq!(attr.type_name("a"), Some(""));
+    assert!(attr.get::<i8>("b").is_err());
+}
+
+#[test]
+fn uni_attribute() {
+    let mut attr = UniAttribute::new(TUP_VERSION).unwrap();
+    attr.put_with_type("a", "int32", &0x12_i32).unwrap();
+    let bytes = hex!(
+        "08 0001"
+        "06 01 61"
+        "18 0001 06 05 696e743332 1d 00 0002 00 12"
+    );
+    assert_eq!(attr.encode(), Ok(bytes.to_vec()));
+
+    let attr = UniAttribute::decode(TUP_VERSION, &bytes).unwrap();
+    assert_eq!(attr.get::<i32>("a"), Ok(Some(0x12)));
+    assert_eq!(attr.type_name("a"), Some("int32"));
+    assert_eq!(attr.get::<i32>("b"), Ok(None));
+}
+
+#[test]
+fn uni_packet() {
+    let mut attr = UniAttribute::new(TUP_VERSION).unwrap();
+    attr.put("args", &vec![1_i8, 2, 3]).unwrap();
+
+    let mut req = RequestPacket::new("A.B.C", "f");
+    req.set_attribute(&attr).unwrap();
+    assert_eq!(req.version, TUP_VERSION);
+
+    let bytes = serde_jce::to_bytes(&req).unwrap();
+    let req: RequestPacket = serde_jce::from_bytes(&bytes).unwrap();
+    assert_eq!(req.attribute(), Ok(attr));
+
+    let mut rsp = ResponsePacket::reply_to(&req);
+    let mut attr = UniAttribute::new(TUP_SIMPLE_VERSION).unwrap();
+    attr.put("", &0_i32).unwrap();
+    rsp.set_attribute(&attr).unwrap();
+    assert_eq!(rsp.version, TUP_SIMPLE_VERSION);
+    assert_eq!(rsp.attribute().unwrap().get::<i32>(""), Ok(Some(0)));
+}
+
+#[test]
+fn uni_packet_type_name() {
+    let mut attr = UniAttribute::new(TUP_VERSION).unwrap();
+    attr.put_with_type("args", "list<char>", &vec![1_i8, 2, 3])
+        .unwrap();
+
+    let mut req = RequestPacket::new("A.B.C", "f");
+    req.set_attribute(&attr).unwrap();
+    let bytes = serde_jce::to_bytes(&req).unwrap();
+    let req: RequestPacket = serde_jce::from_bytes(&bytes).unwrap();
+
+    let attr = req.attribute().unwrap();
+    assert_eq!(attr.version(), TUP_VERSION);
+    assert_eq!(attr.type_name("args"), Some("list<char>"));
+    assert_eq!(attr.get::<Vec<i8>>("args"), Ok(Some(vec![1, 2, 3])));
+}
+
+#[test]
+fn uni_attribute_version() {
+    assert_eq!(
+        UniAttribute::decode(TARS_VERSION, &hex!("08 0c")).map_err(Error::into_kind),
+        Err(serde_jce::ErrorKind::UnknownVersion)
+    );
+    assert_eq!(
+        UniAttribute::new(TARS_VERSION).map_err(Error::into_kind),
+        Err(serde_jce::ErrorKind::UnknownVersion)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// frame test
+
+struct ByteByByte<'a>(&'a [u8]);
+
+impl<'a> std::io::Read for ByteByByte<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((byte, rest)), Some(out)) => {
+                *out = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn frame() {
+    let mut writer = FrameWriter::new(Vec::new());
+    writer
+        .write(&Args {
+            v1: "a".to_owned(),
+            v2: 0x12,
+        })
+        .unwrap();
+    writer.write_frame(&hex!("0c")).unwrap();
+    writer.write_frame(&[]).unwrap();
+    let bytes = writer.into_inner();
+    assert_eq!(
+        bytes,
+        hex!(
+            "00000009 16 01 61 20 12"
+            "00000005 0c"
+            "00000004"
+        )
+    );
+
+    let mut reader = FrameReader::new(ByteByByte(&bytes));
+    assert_eq!(
+        reader.read::<Args>(),
+        Ok(Some(Args {
+            v1: "a".to_owned(),
+            v2: 0x12,
+        }))
+    );
+    assert_eq!(reader.read_frame(), Ok(Some(&hex!("0c")[..])));
+    assert_eq!(reader.read_frame(), Ok(Some(&[][..])));
+    assert_eq!(reader.read_frame(), Ok(None));
+}
+
+#[test]
+fn frame_packet() {
+    let mut req = RequestPacket::new("A.B.C", "f");
+    req.request_id = 0x12;
+
+    let mut writer = FrameWriter::new(Vec::new());
+    writer.write(&req).unwrap();
+    writer.write(&req).unwrap();
+    let bytes = writer.into_inner();
+
+    let mut reader = FrameReader::new(&bytes[..]);
+    assert_eq!(reader.read(), Ok(Some(req.clone())));
+    assert_eq!(reader.read(), Ok(Some(req)));
+    assert_eq!(reader.read::<RequestPacket>(), Ok(None));
+}
+
+#[test]
+fn frame_too_long() {
+    let mut writer = FrameWriter::new(Vec::new());
+    writer.max_frame_len = 8;
+    assert_eq!(writer.write_frame(&hex!("0102 0304")), Ok(()));
+    assert_eq!(
+        writer
+            .write_frame(&hex!("0102 0304 05"))
+            .map_err(Error::into_kind),
+        Err(ErrorKind::FrameTooLong)
+    );
+    assert_eq!(
+        writer.write(&"abcd").map_err(Error::into_kind),
+        Err(ErrorKind::FrameTooLong)
+    );
+    assert_eq!(writer.into_inner(), hex!("00000008 0102 0304"));
+
+    let mut reader = FrameReader::new(&hex!("00000009 0102 0304 05")[..]);
+    reader.max_frame_len = 8;
+    assert_eq!(
+        reader.read_frame().map_err(Error::into_kind),
+        Err(ErrorKind::FrameTooLong)
+    );
+}
+
+#[test]
+fn frame_too_short() {
+    let mut reader = FrameReader::new(&hex!("00000003 0c")[..]);
+    assert_eq!(
+        reader.read_frame().map_err(Error::into_kind),
+        Err(ErrorKind::FrameTooShort)
+    );
+}
+
+#[test]
+fn frame_truncated() {
+    let mut reader = FrameReader::new(ByteByByte(&hex!("0000")));
+    assert_eq!(
+        reader.read_frame().map_err(Error::into_kind),
+        Err(ErrorKind::FrameTruncated)
+    );
+
+    let mut reader = FrameReader::new(ByteByByte(&hex!("00000008 0102")));
+    assert_eq!(
+        reader.read_frame().map_err(Error::into_kind),
+        Err(ErrorKind::FrameTruncated)
+    );
+}
@@ -0,0 +1,173 @@
+use serde_jce::{ErrorKind, TypedValue, Value};
+
+fn round_trip(val: Value, text: &str) {
+    assert_eq!(val.to_text(), text);
+    assert_eq!(Value::from_text(text), Ok(val));
+}
+
+fn typed_round_trip(val: TypedValue, text: &str) {
+    assert_eq!(val.to_text(), text);
+    assert_eq!(TypedValue::from_text(text), Ok(val));
+}
+
+fn text_error(text: &str) -> (usize, usize, String) {
+    match Value::from_text(text).map_err(|e| e.into_kind()) {
+        Err(ErrorKind::Text {
+            line,
+            column,
+            message,
+        }) => (line, column, message),
+        res => panic!("{:?}", res),
+    }
+}
+
+#[test]
+fn scalar() {
+    round_trip(Value::Zero, "zero");
+    round_trip(Value::Int(0), "0");
+    round_trip(Value::Int(i64::MIN), "-9223372036854775808");
+    round_trip(Value::Float(1.1), "1.1f32");
+    round_trip(Value::Double(1.0), "1.0");
+    round_trip(Value::Double(-0.0), "-0.0");
+    round_trip(Value::Double(1e100), "1e100");
+    round_trip(Value::Float(f32::NEG_INFINITY), "-inff32");
+    round_trip(Value::Double(f64::INFINITY), "inf");
+    round_trip(Value::Double(f64::NAN), "nan");
+    round_trip("a\"\\\n\u{1}".into(), r#""a\"\\\n\u{1}""#);
+    round_trip(Value::Bytes(vec![1, 2, 3]), r#"bytes"AQID""#);
+}
+
+#[test]
+fn container() {
+    round_trip(Value::List(vec![]), "[]");
+    round_trip(Value::Map([].into()), "map{}");
+    round_trip(Value::Object([].into()), "{}");
+    round_trip(
+        Value::Object(
+            [
+                (0, Value::List(vec![Value::Int(1), Value::Zero])),
+                (
+                    255,
+                    Value::Map([(Value::Int(1), Value::Object([(1, "a".into())].into()))].into()),
+                ),
+            ]
+            .into(),
+        ),
+        r#"{0: [1, zero], 255: map{1: {1: "a"}}}"#,
+    );
+}
+
+#[test]
+fn suffix() {
+    assert_eq!(
+        Value::from_text("[12i8, -0x12i16, 0x7fffffffi32, 1i64, 1f64, 2f32, 1.5e-1]"),
+        Ok(Value::List(vec![
+            Value::Int(12),
+            Value::Int(-0x12),
+            Value::Int(0x7fffffff),
+            Value::Int(1),
+            Value::Double(1.0),
+            Value::Float(2.0),
+            Value::Double(0.15),
+        ]))
+    );
+    assert_eq!(
+        Value::from_text(r#"string4"a""#),
+        Ok(Value::String("a".to_owned()))
+    );
+}
+
+#[test]
+fn whitespace() {
+    let text = r#"
+        // a comment
+        {
+            0: [1, 2,],
+            1: map { "k" : 1 }, // trailing comma
+        }
+    "#;
+    assert_eq!(
+        Value::from_text(text).map(|v| v.to_text()),
+        Ok(r#"{0: [1, 2], 1: map{"k": 1}}"#.to_owned())
+    );
+}
+
+#[test]
+fn typed() {
+    typed_round_trip(
+        TypedValue::List(vec![
+            TypedValue::I8(0),
+            TypedValue::I16(1),
+            TypedValue::I32(-1),
+            TypedValue::I64(0x12),
+            TypedValue::F32(1.5),
+            TypedValue::F64(f64::NEG_INFINITY),
+            TypedValue::Zero,
+        ]),
+        "[0i8, 1i16, -1i32, 18i64, 1.5f32, -inff64, zero]",
+    );
+    typed_round_trip(
+        TypedValue::Struct(vec![
+            (2, TypedValue::String1("a".to_owned())),
+            (1, TypedValue::String4("b".to_owned())),
+            (0, TypedValue::Bytes(vec![0xff])),
+            (
+                3,
+                TypedValue::Map(vec![(TypedValue::I8(1), TypedValue::I8(2))]),
+            ),
+        ]),
+        r#"{2: "a", 1: string4"b", 0: bytes"/w==", 3: map{1i8: 2i8}}"#,
+    );
+
+    // narrowest type without suffix
+    assert_eq!(
+        TypedValue::from_text("[0, 128, 32768, 2147483648, 1.0]"),
+        Ok(TypedValue::List(vec![
+            TypedValue::I8(0),
+            TypedValue::I16(128),
+            TypedValue::I32(32768),
+            TypedValue::I64(2147483648),
+            TypedValue::F64(1.0),
+        ]))
+    );
+    let long = "a".repeat(256);
+    assert_eq!(
+        TypedValue::from_text(&format!("{:?}", long)),
+        Ok(TypedValue::String4(long))
+    );
+}
+
+#[test]
+fn error() {
+    assert_eq!(text_error("[1, 2"), (1, 6, "expected `]`".to_owned()));
+    assert_eq!(
+        text_error("{\n  256: 1}"),
+        (2, 3, "expected a tag of 0 to 255".to_owned())
+    );
+    assert_eq!(
+        text_error("128i8"),
+        (1, 1, "`128i8` is out of range".to_owned())
+    );
+    assert_eq!(
+        text_error("1.5i8"),
+        (1, 1, "invalid number `1.5i8`".to_owned())
+    );
+    assert_eq!(text_error("foo"), (1, 1, "unexpected `foo`".to_owned()));
+    assert_eq!(text_error("1 2"), (1, 3, "trailing characters".to_owned()));
+    assert_eq!(
+        text_error(r#""abc"#),
+        (1, 1, "unterminated string".to_owned())
+    );
+    assert_eq!(
+        text_error(r#"bytes"!""#),
+        (
+            1,
+            6,
+            "invalid base64: Invalid byte 33, offset 0.".to_owned()
+        )
+    );
+    assert_eq!(
+        Value::from_text("[").unwrap_err().to_string(),
+        "1:2: expected a value"
+    );
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_jce::{ErrorKind, JceType, RawValue, TypedValue, Value};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+enum Enum {
+    Unit,
+    Newtype(i16),
+    Tuple(i8, String),
+    Struct {
+        #[serde(rename = "0")]
+        v0: i8,
+    },
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Struct {
+    #[serde(rename = "0")]
+    v0: i8,
+    #[serde(rename = "1")]
+    v1: String,
+    #[serde(rename = "2", with = "serde_bytes")]
+    v2: Vec<u8>,
+    #[serde(rename = "3")]
+    v3: Vec<f32>,
+    #[serde(rename = "4")]
+    v4: BTreeMap<String, i64>,
+    #[serde(rename = "5")]
+    v5: Vec<Enum>,
+    #[serde(rename = "6")]
+    v6: (u8, f64),
+}
+
+fn sample() -> Struct {
+    Struct {
+        v0: 0x12,
+        v1: "ab".to_owned(),
+        v2: vec![1, 2],
+        v3: vec![1.5],
+        v4: [("k".to_owned(), 0x1234)].into(),
+        v5: vec![
+            Enum::Unit,
+            Enum::Newtype(3),
+            Enum::Tuple(4, "c".to_owned()),
+            Enum::Struct { v0: 5 },
+        ],
+        v6: (0xff, 2.5),
+    }
+}
+
+#[test]
+fn same_as_bytes() {
+    let val = sample();
+    let from_bytes: Value = serde_jce::from_bytes(&serde_jce::to_bytes(&val).unwrap()).unwrap();
+    let mut to_value = serde_jce::to_value(&val).unwrap();
+    // `Enum::Unit` is `Int(0)`, which is written as `Zero`
+    *to_value.pointer_mut("/5/0").unwrap() = Value::Zero;
+    assert_eq!(to_value, from_bytes);
+}
+
+#[test]
+fn round_trip() {
+    let val = sample();
+    let value = serde_jce::to_value(&val).unwrap();
+    assert_eq!(value.pointer("/5/1/1"), Ok(&Value::Int(3)));
+    assert_eq!(value.pointer("/5/3/1/0"), Ok(&Value::Int(5)));
+    assert_eq!(serde_jce::from_value::<Struct>(value), Ok(val));
+}
+
+#[test]
+fn option() {
+    let val = vec![Some(0), None, Some(1)];
+    let value = serde_jce::to_value(&val).unwrap();
+    assert_eq!(
+        value,
+        Value::List(vec![Value::Int(0), Value::Zero, Value::Int(1)])
+    );
+    assert_eq!(serde_jce::from_value::<Vec<Option<i32>>>(value), Ok(val));
+
+    // bytes lose it
+    let bytes = serde_jce::to_bytes(&[Some(0), None]).unwrap();
+    assert_eq!(
+        serde_jce::from_bytes::<Vec<Option<i32>>>(&bytes),
+        Ok(vec![None, None])
+    );
+}
+
+#[test]
+fn zero() {
+    assert_eq!(serde_jce::from_value(Value::Zero), Ok(0_i8));
+    assert_eq!(serde_jce::from_value(Value::Zero), Ok(0.0_f64));
+    assert_eq!(serde_jce::from_value(Value::Zero), Ok(String::new()));
+    assert_eq!(serde_jce::from_value(Value::Zero), Ok(Vec::<i8>::new()));
+    assert_eq!(serde_jce::from_value(Value::Zero), Ok(Enum::Unit));
+}
+
+#[test]
+fn value() {
+    let text = r#"{0: 1, 1: map{2: zero}, 2: [1.5f32, bytes"AQ=="]}"#;
+    let val = Value::from_text(text).unwrap();
+    assert_eq!(serde_jce::to_value(&val).as_ref(), Ok(&val));
+    assert_eq!(
+        serde_jce::from_value::<Value>(Value::from_text(text).unwrap()),
+        Ok(val)
+    );
+}
+
+#[test]
+fn raw() {
+    let raw = RawValue::new(&[0x0a, 0x00, 0x12, 0x0b]).unwrap();
+    assert_eq!(
+        serde_jce::to_value(&raw),
+        Ok(Value::Object([(0, Value::Int(0x12))].into()))
+    );
+
+    let typed = TypedValue::List(vec![TypedValue::I32(1)]);
+    assert_eq!(
+        serde_jce::to_value(&typed),
+        Ok(Value::List(vec![Value::Int(1)]))
+    );
+    assert_eq!(
+        serde_jce::from_value(Value::List(vec![Value::Int(1)])),
+        Ok(TypedValue::List(vec![TypedValue::I8(1)]))
+    );
+}
+
+#[test]
+fn error() {
+    let err = serde_jce::to_value(&u64::MAX).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::IntTooBig);
+
+    let val = Value::from_text(r#"{0: 300, 1: "ab"}"#).unwrap();
+    let err = serde_jce::from_value::<Struct>(val).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::WrongType {
+            expected: &[JceType::Zero, JceType::I8],
+            actual: JceType::I16,
+        }
+    );
+    assert_eq!(err.path(), ".0");
+
+    let val = Value::from_text(r#"{3: [1.5f32, 2.5]}"#).unwrap();
+    let err = serde_jce::from_value::<Struct>(val).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::WrongType {
+            expected: &[JceType::Zero, JceType::F32],
+            actual: JceType::F64,
+        }
+    );
+    assert_eq!(err.path(), ".3[1]");
+
+    let val = Value::from_text(r#"{4: map{"k": "v"}}"#).unwrap();
+    let err = serde_jce::from_value::<Struct>(val).unwrap_err();
+    assert_eq!(err.path(), ".4.k");
+
+    let val = Value::from_text(r#"{1: "ab"}"#).unwrap();
+    let err = serde_jce::from_value::<Struct>(val).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::MissingField {
+            tag: 0,
+            name: "Struct"
+        }
+    );
+
+    let err = serde_jce::from_value::<Struct>(Value::Int(1)).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::WrongType {
+            expected: &[JceType::StructBegin],
+            actual: JceType::I8,
+        }
+    );
+}
@@ -0,0 +1,118 @@
+use hex_literal::hex;
+use serde::{Deserialize, Serialize};
+use serde_jce::{ErrorKind, TypedValue, Value};
+
+fn round_trip(bytes: &[u8], val: TypedValue) {
+    assert_eq!(serde_jce::from_bytes(bytes), Ok(val.clone()));
+    assert_eq!(serde_jce::from_reader(bytes), Ok(val.clone()));
+    assert_eq!(serde_jce::to_bytes(&val), Ok(bytes.to_vec()));
+}
+
+#[test]
+fn int() {
+    round_trip(&hex!("00 00"), TypedValue::I8(0));
+    round_trip(&hex!("0c"), TypedValue::Zero);
+    round_trip(&hex!("01 0012"), TypedValue::I16(0x12));
+    round_trip(&hex!("02 00000012"), TypedValue::I32(0x12));
+    round_trip(&hex!("03 0000000000000012"), TypedValue::I64(0x12));
+}
+
+#[test]
+fn float() {
+    round_trip(&hex!("04 3f800000"), TypedValue::F32(1.0));
+    round_trip(&hex!("05 3ff0000000000000"), TypedValue::F64(1.0));
+}
+
+#[test]
+fn string() {
+    round_trip(&hex!("06 02 6162"), TypedValue::String1("ab".to_owned()));
+    round_trip(
+        &hex!("07 00000002 6162"),
+        TypedValue::String4("ab".to_owned()),
+    );
+    assert_eq!(
+        serde_jce::to_bytes(&TypedValue::String1("a".repeat(256))).map_err(|e| e.into_kind()),
+        Err(ErrorKind::Message("StringTooLong".to_owned()))
+    );
+}
+
+#[test]
+fn bytes() {
+    round_trip(
+        &hex!("0d 00 0002 1234"),
+        TypedValue::Bytes(hex!("1234").to_vec()),
+    );
+}
+
+#[test]
+fn list() {
+    round_trip(
+        &hex!("09 0002 0c 00 00"),
+        TypedValue::List(vec![TypedValue::Zero, TypedValue::I8(0)]),
+    );
+    round_trip(&hex!("09 0c"), TypedValue::List(vec![]));
+}
+
+#[test]
+fn map() {
+    // the order & duplicate keys are kept
+    round_trip(
+        &hex!("08 0003 06 01 62 10 01 06 01 61 10 02 06 01 62 10 03"),
+        TypedValue::Map(vec![
+            (TypedValue::String1("b".to_owned()), TypedValue::I8(1)),
+            (TypedValue::String1("a".to_owned()), TypedValue::I8(2)),
+            (TypedValue::String1("b".to_owned()), TypedValue::I8(3)),
+        ]),
+    );
+}
+
+#[test]
+fn object() {
+    round_trip(
+        &hex!("0a f1 ff 0012 3c 0b"),
+        TypedValue::Struct(vec![(0xff, TypedValue::I16(0x12)), (3, TypedValue::Zero)]),
+    );
+}
+
+#[test]
+fn lossless() {
+    let bytes = hex!("0a 21 0012 10 00 0b");
+    let val: Value = serde_jce::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        serde_jce::to_bytes(&val),
+        Ok(hex!("0a 1c 20 12 0b").to_vec())
+    );
+    let val: TypedValue = serde_jce::from_bytes(&bytes).unwrap();
+    assert_eq!(serde_jce::to_bytes(&val), Ok(bytes.to_vec()));
+}
+
+#[test]
+fn field() {
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Packet {
+        #[serde(rename = "0")]
+        id: i32,
+        #[serde(rename = "1")]
+        body: TypedValue,
+    }
+
+    let bytes = hex!("0a 00 01 1a 02 00000012 0b 0b");
+    let packet = Packet {
+        id: 1,
+        body: TypedValue::Struct(vec![(0, TypedValue::I32(0x12))]),
+    };
+    assert_eq!(serde_jce::from_bytes(&bytes), Ok(packet));
+    assert_eq!(
+        serde_jce::from_bytes::<Packet>(&bytes).and_then(|p| serde_jce::to_bytes(&p)),
+        Ok(bytes.to_vec())
+    );
+}
+
+#[test]
+fn invalid() {
+    assert!(serde_jce::from_bytes::<TypedValue>(&hex!("06 02 ff ff")).is_err());
+    assert_eq!(
+        serde_jce::from_bytes::<TypedValue>(&hex!("0b")).map_err(|e| e.offset()),
+        Err(Some(0))
+    );
+}
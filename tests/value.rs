@@ -1,5 +1,9 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
 use hex_literal::hex;
-use serde_jce::Value;
+use serde_jce::{jce, ErrorKind, JceType, Value};
 
 #[test]
 fn zero() {
@@ -109,3 +113,101 @@ fn obj() {
     assert_eq!(serde_jce::to_bytes(&val), Ok(bytes.to_vec()));
     assert_eq!(serde_jce::from_bytes(&bytes), Ok(val));
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// conversion
+
+#[test]
+fn try_from_int() {
+    assert_eq!(i8::try_from(&Value::Int(-0x12)), Ok(-0x12));
+    assert_eq!(i8::try_from(&Value::Zero), Ok(0));
+    assert_eq!(u64::try_from(&Value::Int(i64::MAX)), Ok(i64::MAX as u64));
+    assert_eq!(
+        i8::try_from(&Value::Int(0x1234)).unwrap_err().kind(),
+        &ErrorKind::IntTooBig
+    );
+    assert_eq!(
+        u32::try_from(&Value::Int(-1)).unwrap_err().kind(),
+        &ErrorKind::IntTooBig
+    );
+    assert_eq!(
+        i64::try_from(&Value::from("1")).unwrap_err().kind(),
+        &ErrorKind::WrongType {
+            expected: &[
+                JceType::Zero,
+                JceType::I8,
+                JceType::I16,
+                JceType::I32,
+                JceType::I64
+            ],
+            actual: JceType::String1,
+        }
+    );
+    assert_eq!(bool::try_from(&Value::Int(1)), Ok(true));
+}
+
+#[test]
+fn try_from_other() {
+    assert_eq!(f32::try_from(&Value::Float(1.5)), Ok(1.5));
+    assert_eq!(f64::try_from(&Value::Float(1.5)), Ok(1.5));
+    assert_eq!(
+        f32::try_from(&Value::Double(1.5)).unwrap_err().kind(),
+        &ErrorKind::WrongType {
+            expected: &[JceType::Zero, JceType::F32],
+            actual: JceType::F64,
+        }
+    );
+    assert_eq!(<&str>::try_from(&Value::from("ab")), Ok("ab"));
+    assert_eq!(String::try_from(&Value::Zero), Ok(String::new()));
+    assert_eq!(<&[u8]>::try_from(&Value::Bytes(vec![1])), Ok(&[1][..]));
+    assert_eq!(Vec::<u8>::try_from(&Value::Zero), Ok(vec![]));
+    assert!(Vec::<u8>::try_from(&Value::List(vec![])).is_err());
+}
+
+#[test]
+fn accessor() {
+    let val = jce!({ 0: 300, 1: "ab", 2: bytes[1], 3: 1.5f32 });
+    assert_eq!(val[0].as_i16(), Some(300));
+    assert_eq!(val[0].as_u8(), None);
+    assert_eq!(val[0].as_str(), None);
+    assert_eq!(val[1].as_str(), Some("ab"));
+    assert_eq!(val[2].as_bytes(), Some(&[1][..]));
+    assert_eq!(val[3].as_f64(), Some(1.5));
+    assert_eq!(Value::Zero.as_bool(), Some(false));
+
+    assert!(val.is_object());
+    assert!(val[0].is_int());
+    assert!(!val[0].is_zero());
+    assert!(val[3].is_float());
+    assert!(Value::default().is_zero());
+
+    assert_eq!(val[0].jce_type(), JceType::I16);
+    assert_eq!(Value::Int(0).jce_type(), JceType::Zero);
+    assert_eq!(val.jce_type(), JceType::StructBegin);
+}
+
+#[test]
+fn from() {
+    assert_eq!(Value::from('a'), Value::from("a"));
+    assert_eq!(Value::from(&[1u8][..]), Value::Bytes(vec![1]));
+    assert_eq!(Value::from(Some(1u16)), Value::Int(1));
+    assert_eq!(Value::from(None::<i8>), Value::Zero);
+}
+
+fn hash(val: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    val.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn clone_hash() {
+    let val = jce!({ 0: [1, 2.5], 1: { "k": f64::NAN } });
+    assert_eq!(val.clone(), val);
+    assert_eq!(hash(&val.clone()), hash(&val));
+    assert_ne!(hash(&Value::Int(0)), hash(&Value::Zero));
+    assert_ne!(hash(&Value::Float(1.0)), hash(&Value::Double(1.0)));
+
+    let set: HashSet<Value> = [Value::Zero, Value::Int(0), Value::Zero].into();
+    assert_eq!(set.len(), 2);
+}
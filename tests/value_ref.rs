@@ -0,0 +1,76 @@
+use hex_literal::hex;
+use serde::Deserialize;
+use serde_jce::{Deserializer, Value, ValueRef};
+
+#[test]
+fn borrowed() {
+    let bytes = hex!("09 0002 06 02 6162 0d 00 0002 1234");
+    let val: ValueRef = serde_jce::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        val,
+        ValueRef::List(vec![ValueRef::Str("ab"), ValueRef::Bytes(&hex!("1234"))])
+    );
+
+    let list = val.list_ref().unwrap();
+    let str = list[0].string_ref().unwrap();
+    let bytes_ = list[1].clone().bytes().unwrap();
+    assert!(bytes.as_ptr_range().contains(&str.as_ptr()));
+    assert!(bytes.as_ptr_range().contains(&bytes_.as_ptr()));
+}
+
+#[test]
+fn map() {
+    // keys are kept in the order of the input
+    let bytes = hex!("08 0002 06 01 62 10 01 06 01 61 10 02");
+    let val: ValueRef = serde_jce::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        val,
+        ValueRef::Map(vec![
+            (ValueRef::Str("b"), ValueRef::Int(1)),
+            (ValueRef::Str("a"), ValueRef::Int(2)),
+        ])
+    );
+    assert_eq!(serde_jce::to_bytes(&val), Ok(bytes.to_vec()));
+    assert_eq!(
+        val.to_owned(),
+        Value::Map([("a".into(), Value::Int(2)), ("b".into(), Value::Int(1))].into())
+    );
+}
+
+#[test]
+fn object() {
+    let bytes = hex!("0a 10 12 26 02 6162 3c 44 3f800000 0b");
+    let val: ValueRef = serde_jce::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        val,
+        ValueRef::Object(vec![
+            (1, ValueRef::Int(0x12)),
+            (2, ValueRef::Str("ab")),
+            (3, ValueRef::Zero),
+            (4, ValueRef::Float(1.0)),
+        ])
+    );
+    assert_eq!(serde_jce::to_bytes(&val), Ok(bytes.to_vec()));
+    assert_eq!(serde_jce::from_bytes::<Value>(&bytes), Ok(val.to_owned()));
+}
+
+#[test]
+fn getter() {
+    let mut val = ValueRef::Str("ab");
+    assert_eq!(val.int_ref(), None);
+    *val.string_mut().unwrap() = "cd";
+    assert_eq!(val.string(), Some("cd"));
+    assert_eq!(ValueRef::Int(1).int(), Some(1));
+    assert_eq!(ValueRef::Double(1.0).double_ref(), Some(&1.0));
+}
+
+#[test]
+fn reader() {
+    let bytes = hex!("00 12");
+    let mut de = Deserializer::from_reader(&bytes[..]);
+    assert_eq!(ValueRef::deserialize(&mut de), Ok(ValueRef::Int(0x12)));
+
+    let bytes = hex!("06 02 6162");
+    let mut de = Deserializer::from_reader(&bytes[..]);
+    assert!(ValueRef::deserialize(&mut de).is_err());
+}